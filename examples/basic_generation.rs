@@ -66,7 +66,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                 println!("Total time: {:.2}s", total_duration as f64 / 1e9);
             }
         }
-        Err(OllamaError::ModelNotFound(model)) => {
+        Err(OllamaError::ModelNotFound(model, _)) => {
             eprintln!("Model '{model}' not found. Available models:");
             let models = client.list_models().await?;
             for model in models.models {
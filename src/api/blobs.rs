@@ -4,7 +4,10 @@ use crate::{
     error::{OllamaError, Result},
     utils::http::HttpClient,
 };
-use std::sync::Arc;
+use reqwest::Body;
+use sha2::{Digest, Sha256};
+use std::{fs::File, io, path::Path, sync::Arc};
+use tokio_util::io::ReaderStream;
 
 /// API implementation for blob management
 pub struct BlobsApi;
@@ -21,20 +24,28 @@ impl BlobsApi {
         match response.status().as_u16() {
             200 => Ok(true),
             404 => Ok(false),
-            status => {
-                Err(OllamaError::ServerError { status, message: "Blob check failed".to_string() })
-            }
+            status => Err(http_client.report_response(
+                &response,
+                OllamaError::ServerError {
+                    status,
+                    message: "Blob check failed".to_string(),
+                    context: None,
+                },
+            )),
         }
     }
 
     /// Create/upload a blob
     ///
+    /// `data` may be bytes or a streaming [`Body`]. A streaming body is sent
+    /// once, without retries, since it cannot be replayed.
+    ///
     /// # Errors
     /// Returns an error if the HTTP request fails or if the server returns an error status.
-    pub async fn create_blob(
+    pub async fn create_blob<B: Into<Body>>(
         http_client: &Arc<HttpClient>,
         digest: &str,
-        data: Vec<u8>,
+        data: B,
     ) -> Result<()> {
         let path = format!("api/blobs/{digest}");
         let response = http_client
@@ -45,14 +56,53 @@ impl BlobsApi {
             .await?;
 
         if !response.status().is_success() {
-            return Err(OllamaError::ServerError {
-                status: response.status().as_u16(),
-                message: response.text().await.unwrap_or_default(),
-            });
+            return Err(http_client.error_for_response(response, None).await);
         }
 
         Ok(())
     }
+
+    /// Upload a local file as a blob unless the server already has it
+    ///
+    /// Returns the file's `sha256:` digest for use in a create request. The
+    /// file is hashed in chunks and, when it must be uploaded, streamed from
+    /// disk, so it is never held in memory as a whole.
+    ///
+    /// # Errors
+    /// Returns an error if the file cannot be read or either HTTP request fails.
+    pub async fn upload_file(http_client: &Arc<HttpClient>, path: &Path) -> Result<String> {
+        let file_path = path.to_path_buf();
+        let digest = blocking(move || {
+            let mut hasher = Sha256::new();
+            io::copy(&mut File::open(file_path)?, &mut hasher)?;
+            Ok(format!("sha256:{:x}", hasher.finalize()))
+        })
+        .await?;
+
+        if !Self::blob_exists(http_client, &digest).await? {
+            log::debug!("Uploading {} as blob {digest}", path.display());
+            let file = tokio::fs::File::open(path).await?;
+            let body = Body::wrap_stream(ReaderStream::new(file));
+            Self::create_blob(http_client, &digest, body).await?;
+        }
+
+        Ok(digest)
+    }
+
+    /// Compute the `sha256:` digest that addresses `data` as a blob
+    pub fn digest(data: &[u8]) -> String {
+        format!("sha256:{:x}", Sha256::digest(data))
+    }
+}
+
+/// Run file IO on the blocking thread pool
+async fn blocking<T: Send + 'static>(
+    f: impl FnOnce() -> io::Result<T> + Send + 'static,
+) -> Result<T> {
+    tokio::task::spawn_blocking(f)
+        .await
+        .map_err(|error| OllamaError::Other(format!("File task failed: {error}")))?
+        .map_err(OllamaError::from)
 }
 
 #[cfg(test)]
@@ -61,7 +111,7 @@ mod tests {
     use crate::{config::ClientConfig, utils::http::HttpClient};
     use wiremock::{
         Mock, MockServer, ResponseTemplate,
-        matchers::{method, path},
+        matchers::{body_bytes, method, path},
     };
 
     #[test]
@@ -126,6 +176,7 @@ mod tests {
 
         let config = ClientConfig {
             base_url: mock_server.uri().parse().unwrap(),
+            max_retries: 0,
             ..ClientConfig::default()
         };
         let http_client = Arc::new(HttpClient::new(config).unwrap());
@@ -133,7 +184,7 @@ mod tests {
         let result = BlobsApi::blob_exists(&http_client, digest).await;
         assert!(result.is_err());
 
-        if let Err(OllamaError::ServerError { status, message }) = result {
+        if let Err(OllamaError::ServerError { status, message, .. }) = result {
             assert_eq!(status, 500);
             assert_eq!(message, "Blob check failed");
         } else {
@@ -308,4 +359,32 @@ mod tests {
             assert!(result);
         }
     }
+
+    #[tokio::test]
+    async fn test_upload_file_streams_missing_blob() {
+        let mock_server = MockServer::start().await;
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("model.gguf");
+        let data: Vec<u8> = (0..100_000u32).map(|i| i as u8).collect();
+        std::fs::write(&file, &data).unwrap();
+        let digest = BlobsApi::digest(&data);
+
+        Mock::given(method("HEAD"))
+            .and(path(format!("/api/blobs/{digest}")))
+            .respond_with(ResponseTemplate::new(404))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("PUT"))
+            .and(path(format!("/api/blobs/{digest}")))
+            .and(body_bytes(data))
+            .respond_with(ResponseTemplate::new(201))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let http_client =
+            Arc::new(HttpClient::new(ClientConfig::new(mock_server.uri()).unwrap()).unwrap());
+        assert_eq!(BlobsApi::upload_file(&http_client, &file).await.unwrap(), digest);
+    }
 }
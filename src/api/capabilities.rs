@@ -0,0 +1,164 @@
+//! Server capability detection
+
+use crate::{
+    error::{OllamaError, Result},
+    types::{Feature, ServerCapabilities, VersionInfo},
+    utils::{http::HttpClient, retry::RetryPolicy},
+};
+use reqwest::StatusCode;
+
+/// API implementation for detecting what a server supports
+pub struct CapabilitiesApi;
+
+impl CapabilitiesApi {
+    /// Detect the server's capabilities
+    ///
+    /// The version is read from `/api/version` once per client and cached.
+    /// Servers without that endpoint, and development builds reporting
+    /// `0.0.0`, are treated as supporting every feature.
+    ///
+    /// # Errors
+    /// Returns an error if the version request fails; the failure is not cached.
+    pub async fn detect(http_client: &HttpClient) -> Result<ServerCapabilities> {
+        let cache = http_client.capability_cache();
+        let version = cache.version(Self::read_version(http_client)).await?;
+        Ok(cache.capabilities(version))
+    }
+
+    /// Check that the server supports every feature a request relies on
+    ///
+    /// Detection failures other than cancellation are ignored, leaving the
+    /// server to reject the request itself.
+    ///
+    /// # Errors
+    /// Returns [`OllamaError::InvalidParameter`] naming the first unsupported feature.
+    pub async fn require(http_client: &HttpClient, features: &[Feature]) -> Result<()> {
+        if features.is_empty() {
+            return Ok(());
+        }
+
+        let capabilities = match Self::detect(http_client).await {
+            Ok(capabilities) => capabilities,
+            Err(error @ OllamaError::Cancelled(_)) => return Err(error),
+            Err(error) => {
+                log::debug!("Could not detect server capabilities: {error}");
+                return Ok(());
+            }
+        };
+
+        match features.iter().find(|feature| !capabilities.supports(**feature)) {
+            Some(&feature) => Err(unsupported(feature, capabilities.version.as_ref())),
+            None => Ok(()),
+        }
+    }
+
+    async fn read_version(http_client: &HttpClient) -> Result<Option<VersionInfo>> {
+        let probe = http_client.with_retry_policy(RetryPolicy::none());
+        let response = probe.get("api/version").await?;
+        if response.status() == StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+        if !response.status().is_success() {
+            return Err(probe.error_for_response(response, None).await);
+        }
+
+        match probe.json::<VersionInfo>(response).await {
+            Ok(version) if version.at_least(0, 0, 1) => Ok(Some(version)),
+            Ok(_) => Ok(None),
+            Err(error @ OllamaError::InvalidResponse(..)) => {
+                log::debug!("Unrecognized server version: {error}");
+                Ok(None)
+            }
+            Err(error) => Err(error),
+        }
+    }
+}
+
+fn unsupported(feature: Feature, version: Option<&VersionInfo>) -> OllamaError {
+    let (major, minor, patch) = feature.min_version();
+    let running =
+        version.map_or_else(String::new, |version| format!(", but the server runs {version}"));
+    OllamaError::InvalidParameter {
+        parameter: feature.parameter().to_string(),
+        reason: format!("{feature} requires Ollama {major}.{minor}.{patch} or later{running}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{builders::chat_builder::ChatBuilder, config::ClientConfig, models::common::Tool};
+    use serde_json::json;
+    use std::sync::Arc;
+    use wiremock::{
+        Mock, MockServer, ResponseTemplate,
+        matchers::{method, path},
+    };
+
+    async fn server_with_version(version: &str) -> MockServer {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api/version"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({"version": version})))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        mock_server
+    }
+
+    fn client_for(mock_server: &MockServer) -> Arc<HttpClient> {
+        Arc::new(HttpClient::new(ClientConfig::new(mock_server.uri()).unwrap()).unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_detection_is_cached_per_client() {
+        let mock_server = server_with_version("0.4.7").await;
+        let client = client_for(&mock_server);
+
+        let capabilities = CapabilitiesApi::detect(&client).await.unwrap();
+        assert!(capabilities.tools && !capabilities.structured_outputs);
+
+        // Scoped copies share the cache, so the version is only requested once
+        let copy = client.with_retry_policy(RetryPolicy::none());
+        assert_eq!(CapabilitiesApi::detect(&copy).await.unwrap(), capabilities);
+    }
+
+    #[tokio::test]
+    async fn test_unsupported_feature_fails_before_sending() {
+        let mock_server = server_with_version("0.2.8").await;
+        Mock::given(method("POST"))
+            .and(path("/api/chat"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(0)
+            .mount(&mock_server)
+            .await;
+
+        let tool = Tool::function("lookup".into(), "Look something up".into(), json!({}));
+        let result = ChatBuilder::new(client_for(&mock_server))
+            .model("m")
+            .add_user_message("hi")
+            .tools(vec![tool])
+            .send()
+            .await;
+
+        let error = result.unwrap_err();
+        assert!(matches!(
+            error,
+            OllamaError::InvalidParameter { parameter, reason }
+                if parameter == "tools" && reason.contains("0.3.0 or later, but the server runs 0.2.8")
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_unknown_version_allows_every_feature() {
+        let mock_server = MockServer::start().await;
+        let client = client_for(&mock_server);
+
+        CapabilitiesApi::require(&client, &[Feature::Thinking]).await.unwrap();
+        assert!(CapabilitiesApi::detect(&client).await.unwrap().version.is_none());
+
+        let mock_server = server_with_version("0.0.0").await;
+        let capabilities = CapabilitiesApi::detect(&client_for(&mock_server)).await.unwrap();
+        assert!(capabilities.version.is_none() && capabilities.thinking);
+    }
+}
@@ -1,11 +1,11 @@
 //! Chat API implementation
 
 use crate::{
-    error::{OllamaError, Result},
+    api::capabilities::CapabilitiesApi,
+    error::Result,
     models::chat::{ChatRequest, ChatResponse},
     utils::http::HttpClient,
 };
-use futures_util::StreamExt;
 use std::sync::Arc;
 
 /// API implementation for chat completions
@@ -21,21 +21,16 @@ impl ChatApi {
         mut request: ChatRequest,
     ) -> Result<ChatResponse> {
         request.stream = Some(false);
+        CapabilitiesApi::require(http_client, &request.required_features()).await?;
 
-        let response = http_client.post("api/chat").json(&request).send().await?;
+        let response =
+            http_client.post("api/chat").json(&request).model(&request.model).send().await?;
 
         if !response.status().is_success() {
-            let status = response.status().as_u16();
-            let message = response.text().await.unwrap_or_default();
-
-            return Err(match status {
-                404 => OllamaError::ModelNotFound(request.model),
-                _ => OllamaError::ServerError { status, message },
-            });
+            return Err(http_client.error_for_response(response, Some(&request.model)).await);
         }
 
-        let chat_response: ChatResponse =
-            response.json().await.map_err(|e| OllamaError::InvalidResponse(e.to_string()))?;
+        let chat_response: ChatResponse = http_client.json(response).await?;
 
         Ok(chat_response)
     }
@@ -49,49 +44,33 @@ impl ChatApi {
         mut request: ChatRequest,
     ) -> Result<impl tokio_stream::Stream<Item = Result<ChatResponse>> + use<>> {
         request.stream = Some(true);
+        CapabilitiesApi::require(http_client, &request.required_features()).await?;
 
-        let response = http_client.post("api/chat").json(&request).send().await?;
+        let response = http_client
+            .post("api/chat")
+            .json(&request)
+            .model(&request.model)
+            .streaming()
+            .send()
+            .await?;
 
         if !response.status().is_success() {
-            let status = response.status().as_u16();
-            return Err(match status {
-                404 => OllamaError::ModelNotFound(request.model),
-                _ => OllamaError::ServerError {
-                    status,
-                    message: "Stream request failed".to_string(),
-                },
-            });
+            return Err(http_client.error_for_response(response, Some(&request.model)).await);
         }
 
-        let stream = response.bytes_stream().map(|chunk| match chunk {
-            Ok(bytes) => {
-                let text = String::from_utf8_lossy(&bytes);
-                for line in text.lines() {
-                    let line = line.trim();
-                    if !line.is_empty() {
-                        match serde_json::from_str::<ChatResponse>(line) {
-                            Ok(response) => return Ok(response),
-                            Err(e) => {
-                                return Err(OllamaError::InvalidResponse(format!(
-                                    "Failed to parse chunk: {e} - Line: {line}"
-                                )));
-                            }
-                        }
-                    }
-                }
-                Err(OllamaError::InvalidResponse("Empty or invalid chunk".to_string()))
-            }
-            Err(e) => Err(OllamaError::StreamError(e.to_string())),
-        });
-
-        Ok(stream)
+        Ok(http_client.ndjson_stream(response))
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::models::chat::MessageRole;
+    use crate::{config::ClientConfig, error::OllamaError, models::chat::MessageRole};
+    use futures_util::StreamExt;
+    use wiremock::{
+        Mock, MockServer, ResponseTemplate,
+        matchers::{method, path},
+    };
 
     #[test]
     fn test_chat_request_creation() {
@@ -106,4 +85,72 @@ mod tests {
         assert_eq!(request.messages[1].role, MessageRole::User);
         assert_eq!(request.stream, Some(false));
     }
+
+    #[tokio::test]
+    async fn test_chat_stream_yields_one_item_per_record() {
+        let mock_server = MockServer::start().await;
+        let body = [
+            r#"{"model":"m","message":{"role":"assistant","content":"Hi"},"done":false}"#,
+            r#"{"model":"m","message":{"role":"assistant","content":" there"},"done":false}"#,
+            "",
+            r#"{"model":"m","message":{"role":"assistant","content":""},"done":true}"#,
+        ]
+        .join("\r\n");
+
+        Mock::given(method("POST"))
+            .and(path("/api/chat"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(body))
+            .mount(&mock_server)
+            .await;
+
+        let config = ClientConfig {
+            base_url: mock_server.uri().parse().unwrap(),
+            ..ClientConfig::default()
+        };
+        let http_client = Arc::new(HttpClient::new(config).unwrap());
+
+        let request = ChatRequest::new("m").add_user_message("Hello");
+        let chunks: Vec<_> =
+            ChatApi::chat_stream(&http_client, request).await.unwrap().collect().await;
+
+        assert_eq!(chunks.len(), 3);
+        let content: String =
+            chunks.iter().map(|c| c.as_ref().unwrap().message.content.as_str()).collect();
+        assert_eq!(content, "Hi there");
+        assert!(chunks[2].as_ref().unwrap().done);
+    }
+
+    #[tokio::test]
+    async fn test_chat_error_bodies_are_classified() {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/api/chat"))
+            .respond_with(ResponseTemplate::new(401).set_body_string(r#"{"error":"unauthorized"}"#))
+            .up_to_n_times(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/api/chat"))
+            .respond_with(ResponseTemplate::new(500).set_body_string(
+                r#"{"error":"llama runner process has terminated: cudaMalloc failed: out of memory"}"#,
+            ))
+            .mount(&mock_server)
+            .await;
+
+        let config = ClientConfig {
+            base_url: mock_server.uri().parse().unwrap(),
+            max_retries: 0,
+            ..ClientConfig::default()
+        };
+        let http_client = Arc::new(HttpClient::new(config).unwrap());
+
+        let request = ChatRequest::new("m").add_user_message("Hello");
+        let result = ChatApi::chat(&http_client, request.clone()).await;
+        assert!(
+            matches!(result, Err(OllamaError::AuthenticationError(ref message, _)) if message == "401 - unauthorized")
+        );
+
+        let result = ChatApi::chat_stream(&http_client, request).await;
+        assert!(matches!(result, Err(OllamaError::InsufficientResources(..))));
+    }
 }
@@ -5,8 +5,10 @@ use crate::{
     models::embedding::{
         EmbedRequest, EmbedResponse, LegacyEmbeddingRequest, LegacyEmbeddingResponse,
     },
+    types::Feature,
     utils::http::HttpClient,
 };
+use reqwest::{Response, StatusCode, header::CONTENT_TYPE};
 use std::sync::Arc;
 
 /// API implementation for embeddings
@@ -15,28 +17,77 @@ pub struct EmbeddingsApi;
 impl EmbeddingsApi {
     /// Generate embeddings using the new API
     ///
+    /// Servers without `/api/embed` are detected from their version or a `404`
+    /// for the endpoint itself, and are sent one legacy `/api/embeddings`
+    /// request per input instead. The result is cached per client. The legacy
+    /// endpoint has no `truncate` setting, so a request that sets it fails with
+    /// [`OllamaError::InvalidParameter`] rather than being sent without it.
+    ///
     /// # Errors
     /// Returns an error if the HTTP request fails, the model is not found, or the server returns an error.
     pub async fn embed(
         http_client: &Arc<HttpClient>,
         request: EmbedRequest,
     ) -> Result<EmbedResponse> {
-        let response = http_client.post("api/embed").json(&request).send().await?;
+        let cache = http_client.capability_cache();
+        if !cache.supports(Feature::Embed) {
+            return Self::embed_with_legacy(http_client, request).await;
+        }
+
+        let response =
+            http_client.post("api/embed").json(&request).model(&request.model).send().await?;
+
+        if endpoint_missing(&response) {
+            log::info!("Server has no /api/embed endpoint, falling back to /api/embeddings");
+            cache.mark_legacy_embeddings();
+            return Self::embed_with_legacy(http_client, request).await;
+        }
 
         if !response.status().is_success() {
-            let status = response.status().as_u16();
-            let message = response.text().await.unwrap_or_default();
+            return Err(http_client.error_for_response(response, Some(&request.model)).await);
+        }
+
+        let embed_response: EmbedResponse = http_client.json(response).await?;
+
+        Ok(embed_response)
+    }
 
-            return Err(match status {
-                404 => OllamaError::ModelNotFound(request.model),
-                _ => OllamaError::ServerError { status, message },
+    async fn embed_with_legacy(
+        http_client: &Arc<HttpClient>,
+        request: EmbedRequest,
+    ) -> Result<EmbedResponse> {
+        if request.truncate.is_some() {
+            return Err(OllamaError::InvalidParameter {
+                parameter: "truncate".to_string(),
+                reason: "truncate requires /api/embed, which the server does not provide"
+                    .to_string(),
             });
         }
 
-        let embed_response: EmbedResponse =
-            response.json().await.map_err(|e| OllamaError::InvalidResponse(e.to_string()))?;
+        let inputs = request.inputs_as_vec();
+        let mut response = EmbedResponse {
+            model: request.model.clone(),
+            embeddings: Vec::with_capacity(inputs.len()),
+            total_duration: None,
+            load_duration: None,
+            prompt_eval_count: None,
+        };
 
-        Ok(embed_response)
+        for prompt in inputs {
+            let legacy = LegacyEmbeddingRequest {
+                model: request.model.clone(),
+                prompt,
+                options: request.options.clone(),
+                keep_alive: request.keep_alive.clone(),
+            };
+            let legacy = Self::embed_legacy(http_client, legacy).await?;
+            response.embeddings.push(legacy.embedding);
+            add(&mut response.total_duration, legacy.total_duration);
+            add(&mut response.load_duration, legacy.load_duration);
+            add(&mut response.prompt_eval_count, legacy.prompt_eval_count);
+        }
+
+        Ok(response)
     }
 
     /// Generate embeddings using the legacy API (deprecated)
@@ -47,28 +98,47 @@ impl EmbeddingsApi {
         http_client: &Arc<HttpClient>,
         request: LegacyEmbeddingRequest,
     ) -> Result<LegacyEmbeddingResponse> {
-        let response = http_client.post("api/embeddings").json(&request).send().await?;
+        let response =
+            http_client.post("api/embeddings").json(&request).model(&request.model).send().await?;
 
         if !response.status().is_success() {
-            let status = response.status().as_u16();
-            let message = response.text().await.unwrap_or_default();
-
-            return Err(match status {
-                404 => OllamaError::ModelNotFound(request.model),
-                _ => OllamaError::ServerError { status, message },
-            });
+            return Err(http_client.error_for_response(response, Some(&request.model)).await);
         }
 
-        let embed_response: LegacyEmbeddingResponse =
-            response.json().await.map_err(|e| OllamaError::InvalidResponse(e.to_string()))?;
+        let embed_response: LegacyEmbeddingResponse = http_client.json(response).await?;
 
         Ok(embed_response)
     }
 }
 
+/// Whether a response says the endpoint does not exist, rather than reporting an Ollama error
+fn endpoint_missing(response: &Response) -> bool {
+    let json = response
+        .headers()
+        .get(CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.starts_with("application/json"));
+    matches!(response.status(), StatusCode::NOT_FOUND | StatusCode::METHOD_NOT_ALLOWED) && !json
+}
+
+fn add<T: std::ops::Add<Output = T>>(total: &mut Option<T>, value: Option<T>) {
+    if let Some(value) = value {
+        *total = Some(match total.take() {
+            Some(total) => total + value,
+            None => value,
+        });
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::config::ClientConfig;
+    use serde_json::json;
+    use wiremock::{
+        Mock, MockServer, ResponseTemplate,
+        matchers::{method, path},
+    };
 
     #[test]
     fn test_embed_request_creation() {
@@ -85,4 +155,83 @@ mod tests {
         assert_eq!(request.input_count(), 3);
         assert_eq!(request.inputs_as_vec(), vec!["text1", "text2", "text3"]);
     }
+
+    #[tokio::test]
+    async fn test_falls_back_to_legacy_endpoint() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/api/embed"))
+            .respond_with(ResponseTemplate::new(404).set_body_string("404 page not found"))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/api/embeddings"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "embedding": [0.1, 0.2],
+                "total_duration": 5
+            })))
+            .expect(3)
+            .mount(&mock_server)
+            .await;
+
+        let client =
+            Arc::new(HttpClient::new(ClientConfig::new(mock_server.uri()).unwrap()).unwrap());
+        let response =
+            EmbeddingsApi::embed(&client, EmbedRequest::new("m", vec!["a", "b"])).await.unwrap();
+        assert_eq!(response.count(), 2);
+        assert_eq!(response.total_duration, Some(10));
+
+        // Later requests go straight to the legacy endpoint
+        let response = EmbeddingsApi::embed(&client, EmbedRequest::new("m", "c")).await.unwrap();
+        assert_eq!(response.model, "m");
+        assert_eq!(response.dimensions(), Some(2));
+    }
+
+    #[tokio::test]
+    async fn test_missing_model_does_not_fall_back() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/api/embed"))
+            .respond_with(
+                ResponseTemplate::new(404).set_body_json(json!({"error": "model \"m\" not found"})),
+            )
+            .mount(&mock_server)
+            .await;
+
+        let client =
+            Arc::new(HttpClient::new(ClientConfig::new(mock_server.uri()).unwrap()).unwrap());
+        let error = EmbeddingsApi::embed(&client, EmbedRequest::new("m", "a")).await.unwrap_err();
+        assert!(matches!(error, OllamaError::ModelNotFound(..)));
+    }
+
+    #[tokio::test]
+    async fn test_legacy_fallback_rejects_truncate() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/api/embed"))
+            .respond_with(ResponseTemplate::new(404).set_body_string("404 page not found"))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/api/embeddings"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({"embedding": [0.1]})))
+            .expect(0)
+            .mount(&mock_server)
+            .await;
+
+        let client =
+            Arc::new(HttpClient::new(ClientConfig::new(mock_server.uri()).unwrap()).unwrap());
+        let mut request = EmbedRequest::new("m", "a");
+        request.truncate = Some(false);
+        let error = EmbeddingsApi::embed(&client, request).await.unwrap_err();
+
+        assert!(matches!(
+            error,
+            OllamaError::InvalidParameter { ref parameter, .. } if parameter == "truncate"
+        ));
+    }
 }
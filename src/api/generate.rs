@@ -1,11 +1,11 @@
 //! Generate API implementation
 
 use crate::{
-    error::{OllamaError, Result},
+    api::capabilities::CapabilitiesApi,
+    error::Result,
     models::generation::{GenerateRequest, GenerateResponse},
     utils::http::HttpClient,
 };
-use futures_util::StreamExt;
 use std::sync::Arc;
 
 /// API implementation for text generation
@@ -21,18 +21,16 @@ impl GenerateApi {
         mut request: GenerateRequest,
     ) -> Result<GenerateResponse> {
         request.stream = Some(false);
+        CapabilitiesApi::require(http_client, &request.required_features()).await?;
 
-        let response = http_client.post("api/generate").json(&request).send().await?;
+        let response =
+            http_client.post("api/generate").json(&request).model(&request.model).send().await?;
 
         if !response.status().is_success() {
-            return Err(OllamaError::ServerError {
-                status: response.status().as_u16(),
-                message: response.text().await.unwrap_or_default(),
-            });
+            return Err(http_client.error_for_response(response, Some(&request.model)).await);
         }
 
-        let generate_response: GenerateResponse =
-            response.json().await.map_err(|e| OllamaError::InvalidResponse(e.to_string()))?;
+        let generate_response: GenerateResponse = http_client.json(response).await?;
 
         Ok(generate_response)
     }
@@ -46,39 +44,61 @@ impl GenerateApi {
         mut request: GenerateRequest,
     ) -> Result<impl tokio_stream::Stream<Item = Result<GenerateResponse>> + use<>> {
         request.stream = Some(true);
+        CapabilitiesApi::require(http_client, &request.required_features()).await?;
 
-        let response = http_client.post("api/generate").json(&request).send().await?;
+        let response = http_client
+            .post("api/generate")
+            .json(&request)
+            .model(&request.model)
+            .streaming()
+            .send()
+            .await?;
 
         if !response.status().is_success() {
-            return Err(OllamaError::ServerError {
-                status: response.status().as_u16(),
-                message: "Stream request failed".to_string(),
-            });
+            return Err(http_client.error_for_response(response, Some(&request.model)).await);
         }
 
-        let stream = response.bytes_stream().map(|chunk| match chunk {
-            Ok(bytes) => {
-                let text = String::from_utf8_lossy(&bytes);
-                for line in text.lines() {
-                    if !line.trim().is_empty() {
-                        match serde_json::from_str::<GenerateResponse>(line) {
-                            Ok(response) => return Ok(response),
-                            Err(e) => return Err(OllamaError::InvalidResponse(e.to_string())),
-                        }
-                    }
-                }
-                Err(OllamaError::InvalidResponse("Empty chunk".to_string()))
-            }
-            Err(e) => Err(OllamaError::StreamError(e.to_string())),
-        });
-
-        Ok(stream)
+        Ok(http_client.ndjson_stream(response))
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::{
+        GenerateStream,
+        config::ClientConfig,
+        error::OllamaError,
+        models::common::ResponseFormat,
+        utils::middleware::{Middleware, Next, map_body_stream},
+    };
+    use futures_util::{StreamExt, future::BoxFuture, stream};
+    use reqwest::{Request, Response};
+    use wiremock::{
+        Mock, MockServer, ResponseTemplate,
+        matchers::{method, path},
+    };
+
+    async fn client_for(body: String) -> (MockServer, Arc<HttpClient>) {
+        client_responding(ResponseTemplate::new(200).set_body_string(body)).await
+    }
+
+    async fn client_responding(response: ResponseTemplate) -> (MockServer, Arc<HttpClient>) {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/api/generate"))
+            .respond_with(response)
+            .mount(&mock_server)
+            .await;
+
+        let config = ClientConfig {
+            base_url: mock_server.uri().parse().unwrap(),
+            max_retries: 0,
+            ..ClientConfig::default()
+        };
+        (mock_server, Arc::new(HttpClient::new(config).unwrap()))
+    }
 
     #[tokio::test]
     async fn test_generate_request_format() {
@@ -90,4 +110,182 @@ mod tests {
         assert_eq!(request.stream, Some(false));
         assert_eq!(request.system, Some("test system".to_string()));
     }
+
+    #[tokio::test]
+    async fn test_generate_stream_yields_every_coalesced_record() {
+        let body = [
+            r#"{"model":"m","response":"Hel","done":false}"#,
+            r#"{"model":"m","response":"lo","done":false}"#,
+            r#"{"model":"m","response":"!","done":true,"eval_count":3}"#,
+        ]
+        .join("\n");
+        let (_server, http_client) = client_for(body).await;
+
+        let request = GenerateRequest::new("m", "hi");
+        let chunks: Vec<_> =
+            GenerateApi::generate_stream(&http_client, request).await.unwrap().collect().await;
+
+        assert_eq!(chunks.len(), 3);
+        let text: String = chunks.iter().map(|c| c.as_ref().unwrap().response.as_str()).collect();
+        assert_eq!(text, "Hello!");
+        assert!(chunks[2].as_ref().unwrap().done);
+    }
+
+    #[tokio::test]
+    async fn test_generate_stream_reassembles_records_split_by_transport() {
+        // Large records are delivered across several transport chunks
+        let large = "x".repeat(512 * 1024);
+        let body = format!(
+            "{{\"model\":\"m\",\"response\":\"{large}\",\"done\":false}}\n\
+             {{\"model\":\"m\",\"response\":\"\",\"done\":true}}\n"
+        );
+        let (_server, http_client) = client_for(body).await;
+
+        let request = GenerateRequest::new("m", "hi");
+        let chunks: Vec<_> =
+            GenerateApi::generate_stream(&http_client, request).await.unwrap().collect().await;
+
+        assert_eq!(chunks.len(), 2);
+        assert_eq!(chunks[0].as_ref().unwrap().response.len(), large.len());
+        assert!(chunks[1].as_ref().unwrap().done);
+    }
+
+    #[tokio::test]
+    async fn test_generate_stream_decodes_byte_by_byte_delivery() {
+        #[derive(Debug)]
+        struct ByteByByte;
+
+        impl Middleware for ByteByByte {
+            fn handle<'a>(
+                &'a self,
+                request: Request,
+                next: Next<'a>,
+            ) -> BoxFuture<'a, Result<Response>> {
+                Box::pin(async move {
+                    let response = next.run(request).await?;
+                    Ok(map_body_stream(response, |body| {
+                        body.flat_map(|chunk| {
+                            let bytes = chunk.unwrap_or_default();
+                            stream::iter(
+                                (0..bytes.len())
+                                    .map(move |i| Ok::<_, reqwest::Error>(bytes.slice(i..=i))),
+                            )
+                        })
+                    }))
+                })
+            }
+        }
+
+        let mock_server = MockServer::start().await;
+        let body = [
+            r#"{"model":"m","response":"Hé","done":false}"#,
+            r#"{"model":"m","response":"llo","done":false}"#,
+            r#" {"done":false, "error":"model runner has unexpectedly stopped"}"#,
+        ]
+        .join("\n");
+        Mock::given(method("POST"))
+            .and(path("/api/generate"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(body))
+            .mount(&mock_server)
+            .await;
+        let config =
+            ClientConfig::builder().base_url(mock_server.uri()).middleware(ByteByByte).build();
+        let http_client = Arc::new(HttpClient::new(config.unwrap()).unwrap());
+
+        let request = GenerateRequest::new("m", "hi");
+        let chunks: Vec<_> =
+            GenerateApi::generate_stream(&http_client, request).await.unwrap().collect().await;
+
+        assert_eq!(chunks.len(), 3);
+        assert_eq!(chunks[0].as_ref().unwrap().response, "Hé");
+        assert_eq!(chunks[1].as_ref().unwrap().response, "llo");
+        assert!(matches!(
+            &chunks[2],
+            Err(OllamaError::StreamError(message, _)) if message == "model runner has unexpectedly stopped"
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_generate_model_not_found() {
+        let body = r#"{"error":"model \"m\" not found, try pulling it first"}"#;
+        let (_server, http_client) =
+            client_responding(ResponseTemplate::new(404).set_body_string(body)).await;
+
+        let result = GenerateApi::generate(&http_client, GenerateRequest::new("m", "hi")).await;
+        assert!(matches!(result, Err(OllamaError::ModelNotFound(ref model, _)) if model == "m"));
+
+        let result =
+            GenerateApi::generate_stream(&http_client, GenerateRequest::new("m", "hi")).await;
+        assert!(matches!(result, Err(OllamaError::ModelNotFound(..))));
+    }
+
+    #[tokio::test]
+    async fn test_generate_stream_setup_error_keeps_server_message() {
+        let (_server, http_client) = client_responding(
+            ResponseTemplate::new(500)
+                .set_body_string(r#"{"error":"llama runner process has terminated"}"#),
+        )
+        .await;
+
+        let result =
+            GenerateApi::generate_stream(&http_client, GenerateRequest::new("m", "hi")).await;
+        let Err(OllamaError::ServerError { status, message, .. }) = result else {
+            panic!("Expected ServerError");
+        };
+        assert_eq!(status, 500);
+        assert_eq!(message, "llama runner process has terminated");
+    }
+
+    #[tokio::test]
+    async fn test_generate_stream_error_record() {
+        let body = [
+            r#"{"model":"m","response":"Hel","done":false}"#,
+            r#"{"error":"an error was encountered while running the model"}"#,
+        ]
+        .join("\n");
+        let (_server, http_client) = client_for(body).await;
+
+        let request = GenerateRequest::new("m", "hi");
+        let chunks: Vec<_> =
+            GenerateApi::generate_stream(&http_client, request).await.unwrap().collect().await;
+
+        assert!(chunks[0].is_ok());
+        assert!(matches!(
+            &chunks[1],
+            Err(OllamaError::StreamError(message, _)) if message.starts_with("an error was encountered")
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_generate_with_thinking_and_schema() {
+        let body = [
+            r#"{"model":"m","response":"","thinking":"Let me ","done":false}"#,
+            r#"{"model":"m","response":"","thinking":"see.","done":false}"#,
+            r#"{"model":"m","response":"{\"a\":1}","done":true}"#,
+        ]
+        .join("\n");
+        let (mock_server, http_client) = client_for(body).await;
+        Mock::given(method("GET"))
+            .and(path("/api/version"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(serde_json::json!({"version": "0.9.0"})),
+            )
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let schema = serde_json::json!({"type": "object"});
+        let request =
+            GenerateRequest::new("m", "hi").think(true).format(ResponseFormat::Schema(schema));
+        let stream = GenerateApi::generate_stream(&http_client, request).await.unwrap();
+        let response = GenerateStream::new(Box::pin(stream)).collect_response().await.unwrap();
+
+        assert_eq!(response.thinking.as_deref(), Some("Let me see."));
+        assert_eq!(response.response, r#"{"a":1}"#);
+
+        let requests = mock_server.received_requests().await.unwrap();
+        let sent: serde_json::Value = requests.last().unwrap().body_json().unwrap();
+        assert_eq!(sent["think"], true);
+        assert_eq!(sent["format"], serde_json::json!({"type": "object"}));
+    }
 }
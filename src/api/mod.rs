@@ -1,6 +1,7 @@
 //! API modules for different Ollama endpoints
 
 pub mod blobs;
+pub mod capabilities;
 pub mod chat;
 pub mod embeddings;
 pub mod generate;
@@ -2,14 +2,22 @@
 
 use crate::{
     error::{OllamaError, Result},
-    models::model_info::{
-        CopyRequest, CreateRequest, DeleteRequest, ModelInfo, ModelList, PullRequest,
-        RunningModels, ShowRequest,
+    models::{
+        model_info::{
+            CopyRequest, CreateModelRequest, CreateProgress, CreateRequest, DeleteRequest,
+            ModelInfo, ModelList, PullProgress, PullRequest, PushProgress, PushRequest,
+            RunningModels, ShowRequest,
+        },
+        model_name::ModelName,
+    },
+    utils::{
+        cancel::run_until_cancelled,
+        http::HttpClient,
+        retry::{ResilientPullOptions, RetryPolicy},
     },
-    utils::http::HttpClient,
 };
-use futures_util::StreamExt;
-use std::sync::Arc;
+use futures_util::{Stream, StreamExt, stream};
+use std::{collections::HashMap, pin::Pin, sync::Arc};
 
 /// API implementation for model management
 pub struct ModelsApi;
@@ -23,14 +31,10 @@ impl ModelsApi {
         let response = http_client.get("api/tags").await?;
 
         if !response.status().is_success() {
-            return Err(OllamaError::ServerError {
-                status: response.status().as_u16(),
-                message: response.text().await.unwrap_or_default(),
-            });
+            return Err(http_client.error_for_response(response, None).await);
         }
 
-        let model_list: ModelList =
-            response.json().await.map_err(|e| OllamaError::InvalidResponse(e.to_string()))?;
+        let model_list: ModelList = http_client.json(response).await?;
 
         Ok(model_list)
     }
@@ -42,21 +46,13 @@ impl ModelsApi {
     pub async fn show_model(http_client: &Arc<HttpClient>, name: &str) -> Result<ModelInfo> {
         let request = ShowRequest { name: name.to_string(), verbose: Some(false) };
 
-        let response = http_client.post("api/show").json(&request).send().await?;
+        let response = http_client.post("api/show").json(&request).model(name).send().await?;
 
         if !response.status().is_success() {
-            let status = response.status().as_u16();
-            return Err(match status {
-                404 => OllamaError::ModelNotFound(name.to_string()),
-                _ => OllamaError::ServerError {
-                    status,
-                    message: response.text().await.unwrap_or_default(),
-                },
-            });
+            return Err(http_client.error_for_response(response, Some(name)).await);
         }
 
-        let model_info: ModelInfo =
-            response.json().await.map_err(|e| OllamaError::InvalidResponse(e.to_string()))?;
+        let model_info: ModelInfo = http_client.json(response).await?;
 
         Ok(model_info)
     }
@@ -68,18 +64,14 @@ impl ModelsApi {
     pub async fn pull_model(http_client: &Arc<HttpClient>, name: &str, stream: bool) -> Result<()> {
         let request = PullRequest { name: name.to_string(), stream: Some(stream), insecure: None };
 
-        let response = http_client.post("api/pull").json(&request).send().await?;
+        let response = http_client.post("api/pull").json(&request).model(name).send().await?;
 
         if !response.status().is_success() {
-            return Err(OllamaError::ServerError {
-                status: response.status().as_u16(),
-                message: response.text().await.unwrap_or_default(),
-            });
+            return Err(http_client.error_for_response(response, Some(name)).await);
         }
 
         if !stream {
-            let _: serde_json::Value =
-                response.json().await.map_err(|e| OllamaError::InvalidResponse(e.to_string()))?;
+            let _: serde_json::Value = http_client.json(response).await?;
         }
 
         Ok(())
@@ -89,35 +81,106 @@ impl ModelsApi {
     pub async fn pull_model_stream(
         http_client: &Arc<HttpClient>,
         name: &str,
-    ) -> Result<impl tokio_stream::Stream<Item = Result<serde_json::Value>>> {
+    ) -> Result<impl tokio_stream::Stream<Item = Result<PullProgress>> + use<>> {
         let request = PullRequest { name: name.to_string(), stream: Some(true), insecure: None };
 
-        let response = http_client.post("api/pull").json(&request).send().await?;
+        let response =
+            http_client.post("api/pull").json(&request).model(name).streaming().send().await?;
 
         if !response.status().is_success() {
-            return Err(OllamaError::ServerError {
-                status: response.status().as_u16(),
-                message: "Pull stream request failed".to_string(),
-            });
+            return Err(http_client.error_for_response(response, Some(name)).await);
         }
 
-        let stream = response.bytes_stream().map(|chunk| match chunk {
-            Ok(bytes) => {
-                let text = String::from_utf8_lossy(&bytes);
-                for line in text.lines() {
-                    if !line.trim().is_empty() {
-                        match serde_json::from_str::<serde_json::Value>(line) {
-                            Ok(progress) => return Ok(progress),
-                            Err(e) => return Err(OllamaError::InvalidResponse(e.to_string())),
-                        }
-                    }
-                }
-                Err(OllamaError::InvalidResponse("Empty chunk".to_string()))
-            }
-            Err(e) => Err(OllamaError::StreamError(e.to_string())),
-        });
+        Ok(http_client.ndjson_stream(response))
+    }
 
-        Ok(stream)
+    /// Pull a model, reconnecting when the progress stream fails
+    ///
+    /// Transport failures, stalled streams, retryable errors and streams that
+    /// end before `success` re-issue the pull after a backoff from
+    /// `options.policy`. Ollama resumes partially downloaded layers, so progress
+    /// records continue where they stopped and a [`ProgressTracker`] fed from
+    /// this stream stays continuous. Failed attempts are not yielded; the last
+    /// error is yielded once `options.policy.max_retries` reconnects in a row
+    /// complete no bytes, or after `options.max_reconnects` reconnects in total.
+    /// A plain [`RetryPolicy`] may be passed for the
+    /// default total. An `{"error": ...}` record from the server ends the pull
+    /// at once, since reissuing it would fail the same way.
+    ///
+    /// [`ProgressTracker`]: crate::streaming::progress::ProgressTracker
+    pub fn pull_model_resilient<O: Into<ResilientPullOptions>>(
+        http_client: &Arc<HttpClient>,
+        name: &str,
+        options: O,
+    ) -> impl tokio_stream::Stream<Item = Result<PullProgress>> + use<O> {
+        let ResilientPullOptions { policy, max_reconnects } = options.into();
+        let pull = ResilientPull {
+            http_client: http_client.clone(),
+            name: name.to_string(),
+            policy,
+            max_reconnects,
+            stream: None,
+            completed: HashMap::new(),
+            failures: 0,
+            reconnects: 0,
+            finished: false,
+        };
+
+        stream::unfold(pull, |mut pull| async move {
+            let item = pull.next().await?;
+            Some((item, pull))
+        })
+    }
+
+    /// Push a model to a registry
+    ///
+    /// The name must include the registry namespace, such as `user/model:tag`.
+    /// Set `insecure` to allow an insecure connection to a private registry.
+    ///
+    /// # Errors
+    /// Returns an error if the HTTP request fails or the server returns an error.
+    pub async fn push_model(
+        http_client: &Arc<HttpClient>,
+        name: &str,
+        insecure: bool,
+    ) -> Result<()> {
+        let request = PushRequest {
+            name: name.to_string(),
+            stream: Some(false),
+            insecure: insecure.then_some(true),
+        };
+
+        let response = http_client.post("api/push").json(&request).model(name).send().await?;
+
+        if !response.status().is_success() {
+            return Err(http_client.error_for_response(response, Some(name)).await);
+        }
+
+        let _: PushProgress = http_client.json(response).await?;
+
+        Ok(())
+    }
+
+    /// Push a model with streaming progress
+    pub async fn push_model_stream(
+        http_client: &Arc<HttpClient>,
+        name: &str,
+        insecure: bool,
+    ) -> Result<impl tokio_stream::Stream<Item = Result<PushProgress>> + use<>> {
+        let request = PushRequest {
+            name: name.to_string(),
+            stream: Some(true),
+            insecure: insecure.then_some(true),
+        };
+
+        let response =
+            http_client.post("api/push").json(&request).model(name).streaming().send().await?;
+
+        if !response.status().is_success() {
+            return Err(http_client.error_for_response(response, Some(name)).await);
+        }
+
+        Ok(http_client.ndjson_stream(response))
     }
 
     /// Create a new model
@@ -134,13 +197,10 @@ impl ModelsApi {
             quantize: None,
         };
 
-        let response = http_client.post("api/create").json(&request).send().await?;
+        let response = http_client.post("api/create").json(&request).model(name).send().await?;
 
         if !response.status().is_success() {
-            return Err(OllamaError::ServerError {
-                status: response.status().as_u16(),
-                message: response.text().await.unwrap_or_default(),
-            });
+            return Err(http_client.error_for_response(response, Some(name)).await);
         }
 
         Ok(())
@@ -151,7 +211,7 @@ impl ModelsApi {
         http_client: &Arc<HttpClient>,
         name: &str,
         modelfile: &str,
-    ) -> Result<impl tokio_stream::Stream<Item = Result<serde_json::Value>>> {
+    ) -> Result<impl tokio_stream::Stream<Item = Result<CreateProgress>> + use<>> {
         let request = CreateRequest {
             name: name.to_string(),
             modelfile: modelfile.to_string(),
@@ -159,32 +219,62 @@ impl ModelsApi {
             quantize: None,
         };
 
-        let response = http_client.post("api/create").json(&request).send().await?;
+        let response =
+            http_client.post("api/create").json(&request).model(name).streaming().send().await?;
 
         if !response.status().is_success() {
-            return Err(OllamaError::ServerError {
-                status: response.status().as_u16(),
-                message: "Create stream request failed".to_string(),
-            });
+            return Err(http_client.error_for_response(response, Some(name)).await);
         }
 
-        let stream = response.bytes_stream().map(|chunk| match chunk {
-            Ok(bytes) => {
-                let text = String::from_utf8_lossy(&bytes);
-                for line in text.lines() {
-                    if !line.trim().is_empty() {
-                        match serde_json::from_str::<serde_json::Value>(line) {
-                            Ok(progress) => return Ok(progress),
-                            Err(e) => return Err(OllamaError::InvalidResponse(e.to_string())),
-                        }
-                    }
-                }
-                Err(OllamaError::InvalidResponse("Empty chunk".to_string()))
-            }
-            Err(e) => Err(OllamaError::StreamError(e.to_string())),
-        });
+        Ok(http_client.ndjson_stream(response))
+    }
+
+    /// Create a model from structured fields
+    ///
+    /// Blobs referenced in `files` and `adapters` must already exist on the
+    /// server; [`CreateModelBuilder`](crate::builders::create_builder::CreateModelBuilder)
+    /// uploads local files first.
+    ///
+    /// # Errors
+    /// Returns an error if the HTTP request fails or the server returns an error.
+    pub async fn create_model_structured(
+        http_client: &Arc<HttpClient>,
+        mut request: CreateModelRequest,
+    ) -> Result<()> {
+        request.stream = Some(false);
+
+        let response =
+            http_client.post("api/create").json(&request).model(&request.model).send().await?;
+
+        if !response.status().is_success() {
+            return Err(http_client.error_for_response(response, Some(&request.model)).await);
+        }
 
-        Ok(stream)
+        let _: CreateProgress = http_client.json(response).await?;
+
+        Ok(())
+    }
+
+    /// Create a model from structured fields with streaming progress
+    pub async fn create_model_structured_stream(
+        http_client: &Arc<HttpClient>,
+        mut request: CreateModelRequest,
+    ) -> Result<impl tokio_stream::Stream<Item = Result<CreateProgress>> + use<>> {
+        request.stream = Some(true);
+
+        let response = http_client
+            .post("api/create")
+            .json(&request)
+            .model(&request.model)
+            .streaming()
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(http_client.error_for_response(response, Some(&request.model)).await);
+        }
+
+        Ok(http_client.ndjson_stream(response))
     }
 
     /// Copy a model
@@ -193,20 +283,15 @@ impl ModelsApi {
         source: &str,
         destination: &str,
     ) -> Result<()> {
+        // The source is checked as the request's model
+        ModelName::parse(destination)?;
         let request =
             CopyRequest { source: source.to_string(), destination: destination.to_string() };
 
-        let response = http_client.post("api/copy").json(&request).send().await?;
+        let response = http_client.post("api/copy").json(&request).model(source).send().await?;
 
         if !response.status().is_success() {
-            let status = response.status().as_u16();
-            return Err(match status {
-                404 => OllamaError::ModelNotFound(source.to_string()),
-                _ => OllamaError::ServerError {
-                    status,
-                    message: response.text().await.unwrap_or_default(),
-                },
-            });
+            return Err(http_client.error_for_response(response, Some(source)).await);
         }
 
         Ok(())
@@ -216,17 +301,10 @@ impl ModelsApi {
     pub async fn delete_model(http_client: &Arc<HttpClient>, name: &str) -> Result<()> {
         let request = DeleteRequest { name: name.to_string() };
 
-        let response = http_client.delete("api/delete").json(&request).send().await?;
+        let response = http_client.delete("api/delete").json(&request).model(name).send().await?;
 
         if !response.status().is_success() {
-            let status = response.status().as_u16();
-            return Err(match status {
-                404 => OllamaError::ModelNotFound(name.to_string()),
-                _ => OllamaError::ServerError {
-                    status,
-                    message: response.text().await.unwrap_or_default(),
-                },
-            });
+            return Err(http_client.error_for_response(response, Some(name)).await);
         }
 
         Ok(())
@@ -237,23 +315,130 @@ impl ModelsApi {
         let response = http_client.get("api/ps").await?;
 
         if !response.status().is_success() {
-            return Err(OllamaError::ServerError {
-                status: response.status().as_u16(),
-                message: response.text().await.unwrap_or_default(),
-            });
+            return Err(http_client.error_for_response(response, None).await);
         }
 
-        let running_models: RunningModels =
-            response.json().await.map_err(|e| OllamaError::InvalidResponse(e.to_string()))?;
+        let running_models: RunningModels = http_client.json(response).await?;
 
         Ok(running_models)
     }
 }
 
+type ProgressStream = Pin<Box<dyn Stream<Item = Result<PullProgress>> + Send>>;
+
+/// State of a pull that reconnects on failure
+struct ResilientPull {
+    http_client: Arc<HttpClient>,
+    name: String,
+    policy: RetryPolicy,
+    /// Most reconnects in total, however much progress each one makes
+    max_reconnects: u32,
+    stream: Option<ProgressStream>,
+    /// Most bytes seen for each layer, to tell progress from replayed records
+    completed: HashMap<String, u64>,
+    /// Reconnects since bytes were last completed
+    failures: u32,
+    /// Reconnects in total
+    reconnects: u32,
+    finished: bool,
+}
+
+impl ResilientPull {
+    async fn next(&mut self) -> Option<Result<PullProgress>> {
+        while !self.finished {
+            let stream = match &mut self.stream {
+                Some(stream) => stream,
+                None => match ModelsApi::pull_model_stream(&self.http_client, &self.name).await {
+                    Ok(stream) => self.stream.insert(Box::pin(stream)),
+                    Err(error) => match self.reconnect(error, false).await {
+                        Ok(()) => continue,
+                        Err(error) => return Some(Err(error)),
+                    },
+                },
+            };
+
+            let (error, ended) = match stream.next().await {
+                Some(Ok(progress)) => {
+                    self.record(&progress);
+                    return Some(Ok(progress));
+                }
+                Some(Err(error)) => (error, false),
+                None => (
+                    OllamaError::StreamError("Pull ended before it succeeded".to_string(), None),
+                    true,
+                ),
+            };
+            self.stream = None;
+            if let Err(error) = self.reconnect(error, ended).await {
+                return Some(Err(error));
+            }
+        }
+        None
+    }
+
+    fn record(&mut self, progress: &PullProgress) {
+        if progress.status == "success" {
+            self.finished = true;
+        }
+        if let (Some(digest), Some(completed)) = (&progress.digest, progress.completed) {
+            let best = self.completed.entry(digest.clone()).or_default();
+            if completed > *best {
+                *best = completed;
+                self.failures = 0;
+            }
+        }
+    }
+
+    /// Wait before the next attempt, or give up with `error`
+    ///
+    /// `ended` marks a stream that closed cleanly before `success`. A
+    /// [`OllamaError::StreamError`] otherwise is an error record from the
+    /// server, which is not retried.
+    async fn reconnect(&mut self, error: OllamaError, ended: bool) -> Result<()> {
+        let reconnectable = ended
+            || error.is_retryable()
+            || matches!(
+                error,
+                OllamaError::StreamIdleTimeout(..) | OllamaError::FirstChunkTimeout(..)
+            );
+        if !reconnectable
+            || self.failures >= self.policy.max_retries
+            || self.reconnects >= self.max_reconnects
+        {
+            self.finished = true;
+            return Err(error);
+        }
+
+        let delay = self.policy.delay_for(self.failures, error.retry_after());
+        let delay = delay.unwrap_or(self.policy.max_delay);
+        self.failures += 1;
+        self.reconnects += 1;
+        log::warn!(
+            "Pull of {} failed ({error}), reconnecting in {delay:?} (attempt {}/{})",
+            self.name,
+            self.failures,
+            self.policy.max_retries
+        );
+        let token = self.http_client.cancellation_token();
+        run_until_cancelled(token, async {
+            tokio::time::sleep(delay).await;
+            Ok(())
+        })
+        .await
+        .inspect_err(|_| self.finished = true)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::config::ClientConfig;
+    use crate::{
+        config::ClientConfig,
+        error::OllamaError,
+        streaming::progress::{ProgressState, ProgressTracker},
+    };
+    use serde_json::json;
+    use std::time::Duration;
     use wiremock::{
         Mock, MockServer, ResponseTemplate,
         matchers::{body_json, method, path},
@@ -321,6 +506,7 @@ mod tests {
 
         let config = ClientConfig {
             base_url: mock_server.uri().parse().unwrap(),
+            max_retries: 0,
             ..ClientConfig::default()
         };
         let http_client = Arc::new(HttpClient::new(config).unwrap());
@@ -328,7 +514,7 @@ mod tests {
         let result = ModelsApi::list_models(&http_client).await;
         assert!(result.is_err());
 
-        if let Err(OllamaError::ServerError { status, message }) = result {
+        if let Err(OllamaError::ServerError { status, message, .. }) = result {
             assert_eq!(status, 500);
             assert_eq!(message, "Internal Server Error");
         } else {
@@ -354,7 +540,7 @@ mod tests {
 
         let result = ModelsApi::list_models(&http_client).await;
         assert!(result.is_err());
-        assert!(matches!(result.unwrap_err(), OllamaError::InvalidResponse(_)));
+        assert!(matches!(result.unwrap_err(), OllamaError::InvalidResponse(..)));
     }
 
     #[tokio::test]
@@ -418,7 +604,7 @@ mod tests {
 
         let result = ModelsApi::show_model(&http_client, "nonexistent:model").await;
         assert!(result.is_err());
-        assert!(matches!(result.unwrap_err(), OllamaError::ModelNotFound(_)));
+        assert!(matches!(result.unwrap_err(), OllamaError::ModelNotFound(..)));
     }
 
     #[tokio::test]
@@ -488,7 +674,7 @@ mod tests {
         let result = ModelsApi::pull_model(&http_client, "invalid-model", false).await;
         assert!(result.is_err());
 
-        if let Err(OllamaError::ServerError { status, message }) = result {
+        if let Err(OllamaError::ServerError { status, message, .. }) = result {
             assert_eq!(status, 400);
             assert_eq!(message, "Bad Request");
         } else {
@@ -496,6 +682,83 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_push_model_success() {
+        let mock_server = MockServer::start().await;
+
+        let expected_request = PushRequest {
+            name: "team/llama3:v2".to_string(),
+            stream: Some(false),
+            insecure: Some(true),
+        };
+
+        Mock::given(method("POST"))
+            .and(path("/api/push"))
+            .and(body_json(&expected_request))
+            .respond_with(ResponseTemplate::new(200).set_body_string("{\"status\":\"success\"}"))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let http_client =
+            Arc::new(HttpClient::new(ClientConfig::new(mock_server.uri()).unwrap()).unwrap());
+
+        ModelsApi::push_model(&http_client, "team/llama3:v2", true).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_push_model_stream_reports_progress() {
+        let mock_server = MockServer::start().await;
+        let body = concat!(
+            "{\"status\":\"retrieving manifest\"}\n",
+            "{\"status\":\"starting upload\",\"digest\":\"sha256:a\",\"total\":8}\n",
+            "{\"status\":\"starting upload\",\"digest\":\"sha256:a\",\"total\":8,\"completed\":8}\n",
+            "{\"status\":\"pushing manifest\"}\n",
+            "{\"status\":\"success\"}\n",
+        );
+
+        Mock::given(method("POST"))
+            .and(path("/api/push"))
+            .and(body_json(json!({"name": "team/llama3", "stream": true})))
+            .respond_with(ResponseTemplate::new(200).set_body_string(body))
+            .mount(&mock_server)
+            .await;
+
+        let http_client =
+            Arc::new(HttpClient::new(ClientConfig::new(mock_server.uri()).unwrap()).unwrap());
+
+        let mut tracker = ProgressTracker::new();
+        let mut stream =
+            ModelsApi::push_model_stream(&http_client, "team/llama3", false).await.unwrap();
+        while let Some(progress) = stream.next().await {
+            tracker.update(&progress.unwrap());
+        }
+
+        assert_eq!(tracker.total_bytes(), 8);
+        assert_eq!(tracker.fraction(), Some(1.0));
+        assert_eq!(tracker.state(), &ProgressState::Succeeded);
+    }
+
+    #[tokio::test]
+    async fn test_push_model_unauthorized() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/api/push"))
+            .respond_with(
+                ResponseTemplate::new(401)
+                    .set_body_string("{\"error\":\"unauthorized: authentication required\"}"),
+            )
+            .mount(&mock_server)
+            .await;
+
+        let http_client =
+            Arc::new(HttpClient::new(ClientConfig::new(mock_server.uri()).unwrap()).unwrap());
+
+        let error = ModelsApi::push_model(&http_client, "team/llama3", false).await.unwrap_err();
+        assert!(matches!(error, OllamaError::AuthenticationError(..)));
+    }
+
     #[tokio::test]
     async fn test_create_model_success() {
         let mock_server = MockServer::start().await;
@@ -574,7 +837,7 @@ mod tests {
 
         let result = ModelsApi::copy_model(&http_client, "nonexistent:model", "backup").await;
         assert!(result.is_err());
-        assert!(matches!(result.unwrap_err(), OllamaError::ModelNotFound(_)));
+        assert!(matches!(result.unwrap_err(), OllamaError::ModelNotFound(..)));
     }
 
     #[tokio::test]
@@ -618,7 +881,7 @@ mod tests {
 
         let result = ModelsApi::delete_model(&http_client, "nonexistent:model").await;
         assert!(result.is_err());
-        assert!(matches!(result.unwrap_err(), OllamaError::ModelNotFound(_)));
+        assert!(matches!(result.unwrap_err(), OllamaError::ModelNotFound(..)));
     }
 
     #[tokio::test]
@@ -688,6 +951,7 @@ mod tests {
 
         let config = ClientConfig {
             base_url: mock_server.uri().parse().unwrap(),
+            max_retries: 0,
             ..ClientConfig::default()
         };
         let http_client = Arc::new(HttpClient::new(config).unwrap());
@@ -695,7 +959,7 @@ mod tests {
         let result = ModelsApi::list_running_models(&http_client).await;
         assert!(result.is_err());
 
-        if let Err(OllamaError::ServerError { status, message }) = result {
+        if let Err(OllamaError::ServerError { status, message, .. }) = result {
             assert_eq!(status, 503);
             assert_eq!(message, "Service Unavailable");
         } else {
@@ -742,4 +1006,231 @@ mod tests {
         assert_eq!(request.name, "model-to-show");
         assert_eq!(request.verbose, Some(true));
     }
+
+    #[tokio::test]
+    async fn test_pull_model_stream_yields_every_progress_record() {
+        let mock_server = MockServer::start().await;
+        let body = concat!(
+            "{\"status\":\"pulling manifest\"}\n",
+            "{\"status\":\"downloading\",\"digest\":\"sha256:a\",\"total\":10,\"completed\":5}\n",
+            "{\"status\":\"success\"}\n"
+        );
+
+        Mock::given(method("POST"))
+            .and(path("/api/pull"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(body))
+            .mount(&mock_server)
+            .await;
+
+        let config = ClientConfig {
+            base_url: mock_server.uri().parse().unwrap(),
+            ..ClientConfig::default()
+        };
+        let http_client = Arc::new(HttpClient::new(config).unwrap());
+
+        let items: Vec<_> =
+            ModelsApi::pull_model_stream(&http_client, "llama3").await.unwrap().collect().await;
+
+        assert_eq!(items.len(), 3);
+        assert_eq!(items[1].as_ref().unwrap().completed, Some(5));
+        assert_eq!(items[2].as_ref().unwrap().status, "success");
+    }
+
+    #[tokio::test]
+    async fn test_create_model_stream_yields_every_progress_record() {
+        let mock_server = MockServer::start().await;
+        let body = "{\"status\":\"reading model metadata\"}\n{\"status\":\"writing manifest\"}\n{\"status\":\"success\"}";
+
+        Mock::given(method("POST"))
+            .and(path("/api/create"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(body))
+            .mount(&mock_server)
+            .await;
+
+        let config = ClientConfig {
+            base_url: mock_server.uri().parse().unwrap(),
+            ..ClientConfig::default()
+        };
+        let http_client = Arc::new(HttpClient::new(config).unwrap());
+
+        let items: Vec<_> = ModelsApi::create_model_stream(&http_client, "custom", "FROM llama3")
+            .await
+            .unwrap()
+            .collect()
+            .await;
+
+        let statuses: Vec<_> =
+            items.iter().map(|item| item.as_ref().unwrap().status.clone()).collect();
+        assert_eq!(statuses, ["reading model metadata", "writing manifest", "success"]);
+    }
+
+    #[tokio::test]
+    async fn test_pull_progress_error_record_fails_tracker() {
+        let mock_server = MockServer::start().await;
+        let body = concat!(
+            "{\"status\":\"pulling sha256:a\",\"digest\":\"sha256:a\",\"total\":10,\"completed\":4}\n",
+            "{\"error\":\"max retries exceeded\"}\n"
+        );
+
+        Mock::given(method("POST"))
+            .and(path("/api/pull"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(body))
+            .mount(&mock_server)
+            .await;
+
+        let config = ClientConfig {
+            base_url: mock_server.uri().parse().unwrap(),
+            ..ClientConfig::default()
+        };
+        let http_client = Arc::new(HttpClient::new(config).unwrap());
+
+        let mut tracker = ProgressTracker::new();
+        let mut stream = ModelsApi::pull_model_stream(&http_client, "llama3").await.unwrap();
+        while let Some(progress) = stream.next().await {
+            match progress {
+                Ok(progress) => tracker.update(&progress),
+                Err(error) => tracker.fail(&error),
+            }
+        }
+
+        assert_eq!(tracker.fraction(), Some(0.4));
+        assert!(matches!(
+            tracker.state(),
+            ProgressState::Failed(message) if message.contains("max retries exceeded")
+        ));
+    }
+
+    fn reconnecting(max_retries: u32) -> RetryPolicy {
+        RetryPolicy { max_retries, base_delay: Duration::from_millis(1), ..RetryPolicy::none() }
+    }
+
+    #[tokio::test]
+    async fn test_pull_model_resilient_resumes_after_drop() {
+        let mock_server = MockServer::start().await;
+        let dropped = concat!(
+            "{\"status\":\"pulling manifest\"}\n",
+            "{\"status\":\"pulling sha256:a\",\"digest\":\"sha256:a\",\"total\":10,\"completed\":4}\n",
+        );
+        let resumed = concat!(
+            "{\"status\":\"pulling manifest\"}\n",
+            "{\"status\":\"pulling sha256:a\",\"digest\":\"sha256:a\",\"total\":10,\"completed\":4}\n",
+            "{\"status\":\"pulling sha256:a\",\"digest\":\"sha256:a\",\"total\":10,\"completed\":10}\n",
+            "{\"status\":\"success\"}\n",
+        );
+
+        Mock::given(method("POST"))
+            .and(path("/api/pull"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(dropped))
+            .up_to_n_times(1)
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/api/pull"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(resumed))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let http_client =
+            Arc::new(HttpClient::new(ClientConfig::new(mock_server.uri()).unwrap()).unwrap());
+        let mut tracker = ProgressTracker::new();
+        let mut fractions = Vec::new();
+        let mut stream =
+            Box::pin(ModelsApi::pull_model_resilient(&http_client, "llama3", reconnecting(1)));
+        while let Some(progress) = stream.next().await {
+            tracker.update(&progress.unwrap());
+            fractions.extend(tracker.fraction());
+        }
+
+        assert_eq!(tracker.state(), &ProgressState::Succeeded);
+        assert!(fractions.is_sorted());
+        assert_eq!(fractions.last(), Some(&1.0));
+    }
+
+    #[tokio::test]
+    async fn test_pull_model_resilient_gives_up_without_progress() {
+        let mock_server = MockServer::start().await;
+        let body = "{\"status\":\"pulling sha256:a\",\"digest\":\"sha256:a\",\"total\":10,\"completed\":4}\n";
+
+        Mock::given(method("POST"))
+            .and(path("/api/pull"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(body))
+            .expect(3)
+            .mount(&mock_server)
+            .await;
+
+        let http_client =
+            Arc::new(HttpClient::new(ClientConfig::new(mock_server.uri()).unwrap()).unwrap());
+        let items: Vec<_> =
+            ModelsApi::pull_model_resilient(&http_client, "llama3", reconnecting(2))
+                .collect()
+                .await;
+
+        assert_eq!(items.len(), 4);
+        assert!(items[..3].iter().all(Result::is_ok));
+        assert!(matches!(items[3].as_ref().unwrap_err(), OllamaError::StreamError(..)));
+    }
+
+    #[tokio::test]
+    async fn test_pull_model_resilient_stops_on_error_record() {
+        let mock_server = MockServer::start().await;
+        let body = concat!(
+            "{\"status\":\"pulling manifest\"}\n",
+            "{\"error\":\"pull model manifest: file does not exist\"}\n",
+        );
+
+        Mock::given(method("POST"))
+            .and(path("/api/pull"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(body))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let http_client =
+            Arc::new(HttpClient::new(ClientConfig::new(mock_server.uri()).unwrap()).unwrap());
+        let items: Vec<_> =
+            ModelsApi::pull_model_resilient(&http_client, "llama3", reconnecting(3))
+                .collect()
+                .await;
+
+        assert_eq!(items.len(), 2);
+        assert!(matches!(
+            items[1].as_ref().unwrap_err(),
+            OllamaError::StreamError(message, _) if message == "pull model manifest: file does not exist"
+        ));
+    }
+
+    /// Answers each pull with one more completed byte than the last
+    struct GrowingPull(std::sync::atomic::AtomicU64);
+
+    impl wiremock::Respond for GrowingPull {
+        fn respond(&self, _: &wiremock::Request) -> ResponseTemplate {
+            let completed = self.0.fetch_add(1, std::sync::atomic::Ordering::Relaxed) + 1;
+            ResponseTemplate::new(200).set_body_string(format!(
+                "{{\"status\":\"pulling sha256:a\",\"digest\":\"sha256:a\",\"total\":1000,\"completed\":{completed}}}\n"
+            ))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_pull_model_resilient_caps_total_reconnects() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/api/pull"))
+            .respond_with(GrowingPull(Default::default()))
+            .expect(6)
+            .mount(&mock_server)
+            .await;
+
+        let http_client =
+            Arc::new(HttpClient::new(ClientConfig::new(mock_server.uri()).unwrap()).unwrap());
+        let options = ResilientPullOptions { policy: reconnecting(1), max_reconnects: 5 };
+        let items: Vec<_> =
+            ModelsApi::pull_model_resilient(&http_client, "llama3", options).collect().await;
+
+        assert_eq!(items.len(), 7);
+        assert!(items.last().unwrap().is_err());
+    }
 }
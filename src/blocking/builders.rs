@@ -0,0 +1,391 @@
+//! Blocking builders for generate, chat, and create requests
+
+use crate::{
+    blocking::stream::{BlockingIter, ChatStream, GenerateStream},
+    error::Result,
+    models::{
+        chat::{ChatMessage, ChatResponse, ToolChoice},
+        common::{KeepAlive, Options, ResponseFormat, Tool},
+        generation::GenerateResponse,
+        model_info::CreateProgress,
+        options::{RequestOptions, StreamOptions},
+    },
+    utils::cancel::CancellationToken,
+};
+use std::{path::PathBuf, sync::Arc};
+use tokio::runtime::Runtime;
+
+/// Blocking builder for generate requests
+#[derive(Debug, Clone)]
+pub struct GenerateBuilder {
+    inner: crate::GenerateBuilder,
+    runtime: Arc<Runtime>,
+}
+
+impl GenerateBuilder {
+    pub(crate) fn new(inner: crate::GenerateBuilder, runtime: Arc<Runtime>) -> Self {
+        Self { inner, runtime }
+    }
+
+    fn map(self, f: impl FnOnce(crate::GenerateBuilder) -> crate::GenerateBuilder) -> Self {
+        Self { inner: f(self.inner), runtime: self.runtime }
+    }
+
+    /// Set the model to use
+    pub fn model<S: Into<String>>(self, model: S) -> Self {
+        self.map(|inner| inner.model(model))
+    }
+
+    /// Set the prompt
+    pub fn prompt<S: Into<String>>(self, prompt: S) -> Self {
+        self.map(|inner| inner.prompt(prompt))
+    }
+
+    /// Set the system message
+    pub fn system<S: Into<String>>(self, system: S) -> Self {
+        self.map(|inner| inner.system(system))
+    }
+
+    /// Set the template
+    pub fn template<S: Into<String>>(self, template: S) -> Self {
+        self.map(|inner| inner.template(template))
+    }
+
+    /// Set the context for conversation continuity
+    pub fn context(self, context: Vec<i32>) -> Self {
+        self.map(|inner| inner.context(context))
+    }
+
+    /// Set generation options
+    pub fn options(self, options: Options) -> Self {
+        self.map(|inner| inner.options(options))
+    }
+
+    /// Set temperature
+    pub fn temperature(self, temperature: f64) -> Self {
+        self.map(|inner| inner.temperature(temperature))
+    }
+
+    /// Set max tokens
+    pub fn max_tokens(self, max_tokens: u32) -> Self {
+        self.map(|inner| inner.max_tokens(max_tokens))
+    }
+
+    /// Set top-k
+    pub fn top_k(self, top_k: i32) -> Self {
+        self.map(|inner| inner.top_k(top_k))
+    }
+
+    /// Set top-p
+    pub fn top_p(self, top_p: f64) -> Self {
+        self.map(|inner| inner.top_p(top_p))
+    }
+
+    /// Set response format
+    pub fn format(self, format: ResponseFormat) -> Self {
+        self.map(|inner| inner.format(format))
+    }
+
+    /// Set raw mode
+    pub fn raw(self, raw: bool) -> Self {
+        self.map(|inner| inner.raw(raw))
+    }
+
+    /// Set keep alive
+    pub fn keep_alive(self, keep_alive: KeepAlive) -> Self {
+        self.map(|inner| inner.keep_alive(keep_alive))
+    }
+
+    /// Add images for multimodal models
+    pub fn images(self, images: Vec<String>) -> Self {
+        self.map(|inner| inner.images(images))
+    }
+
+    /// Ask a reasoning model to return its thinking separately
+    pub fn think(self, think: bool) -> Self {
+        self.map(|inner| inner.think(think))
+    }
+
+    /// Override the timeout, retries or headers for this request only
+    pub fn request_options(self, options: RequestOptions) -> Self {
+        self.map(|inner| inner.request_options(options))
+    }
+
+    /// Override streaming behavior such as the idle timeout between chunks
+    pub fn stream_options(self, options: StreamOptions) -> Self {
+        self.map(|inner| inner.stream_options(options))
+    }
+
+    /// Cancel the request or stream when the token is cancelled, for example from another thread
+    pub fn cancellation_token(self, token: CancellationToken) -> Self {
+        self.map(|inner| inner.cancellation_token(token))
+    }
+
+    /// Send the request (non-streaming)
+    pub fn send(self) -> Result<GenerateResponse> {
+        self.runtime.block_on(self.inner.send())
+    }
+
+    /// Send the request with streaming
+    pub fn stream(self) -> Result<GenerateStream> {
+        let stream = self.runtime.block_on(self.inner.stream())?;
+        Ok(GenerateStream::new(stream, self.runtime))
+    }
+}
+
+/// Blocking builder for chat requests
+#[derive(Debug, Clone)]
+pub struct ChatBuilder {
+    inner: crate::ChatBuilder,
+    runtime: Arc<Runtime>,
+}
+
+impl ChatBuilder {
+    pub(crate) fn new(inner: crate::ChatBuilder, runtime: Arc<Runtime>) -> Self {
+        Self { inner, runtime }
+    }
+
+    fn map(self, f: impl FnOnce(crate::ChatBuilder) -> crate::ChatBuilder) -> Self {
+        Self { inner: f(self.inner), runtime: self.runtime }
+    }
+
+    /// Set the model to use
+    #[must_use]
+    pub fn model<S: Into<String>>(self, model: S) -> Self {
+        self.map(|inner| inner.model(model))
+    }
+
+    /// Add a message to the conversation
+    pub fn add_message(self, message: ChatMessage) -> Self {
+        self.map(|inner| inner.add_message(message))
+    }
+
+    /// Add a system message
+    pub fn add_system_message<S: Into<String>>(self, content: S) -> Self {
+        self.map(|inner| inner.add_system_message(content))
+    }
+
+    /// Add a user message
+    pub fn add_user_message<S: Into<String>>(self, content: S) -> Self {
+        self.map(|inner| inner.add_user_message(content))
+    }
+
+    /// Add an assistant message
+    pub fn add_assistant_message<S: Into<String>>(self, content: S) -> Self {
+        self.map(|inner| inner.add_assistant_message(content))
+    }
+
+    /// Add a user message with images
+    pub fn add_user_message_with_images<S: Into<String>>(
+        self,
+        content: S,
+        images: Vec<String>,
+    ) -> Self {
+        self.map(|inner| inner.add_user_message_with_images(content, images))
+    }
+
+    /// Set all messages at once
+    pub fn messages(self, messages: Vec<ChatMessage>) -> Self {
+        self.map(|inner| inner.messages(messages))
+    }
+
+    /// Set generation options
+    pub fn options(self, options: Options) -> Self {
+        self.map(|inner| inner.options(options))
+    }
+
+    /// Set temperature
+    pub fn temperature(self, temperature: f64) -> Self {
+        self.map(|inner| inner.temperature(temperature))
+    }
+
+    /// Set max tokens
+    pub fn max_tokens(self, max_tokens: u32) -> Self {
+        self.map(|inner| inner.max_tokens(max_tokens))
+    }
+
+    /// Set top-k
+    pub fn top_k(self, top_k: i32) -> Self {
+        self.map(|inner| inner.top_k(top_k))
+    }
+
+    /// Set top-p
+    pub fn top_p(self, top_p: f64) -> Self {
+        self.map(|inner| inner.top_p(top_p))
+    }
+
+    /// Set response format
+    pub fn format(self, format: ResponseFormat) -> Self {
+        self.map(|inner| inner.format(format))
+    }
+
+    /// Set keep alive
+    pub fn keep_alive(self, keep_alive: KeepAlive) -> Self {
+        self.map(|inner| inner.keep_alive(keep_alive))
+    }
+
+    /// Set available tools
+    pub fn tools(self, tools: Vec<Tool>) -> Self {
+        self.map(|inner| inner.tools(tools))
+    }
+
+    /// Set tool choice strategy
+    pub fn tool_choice(self, choice: ToolChoice) -> Self {
+        self.map(|inner| inner.tool_choice(choice))
+    }
+
+    /// Ask a reasoning model to return its thinking separately
+    pub fn think(self, think: bool) -> Self {
+        self.map(|inner| inner.think(think))
+    }
+
+    /// Override the timeout, retries or headers for this request only
+    pub fn request_options(self, options: RequestOptions) -> Self {
+        self.map(|inner| inner.request_options(options))
+    }
+
+    /// Override streaming behavior such as the idle timeout between chunks
+    pub fn stream_options(self, options: StreamOptions) -> Self {
+        self.map(|inner| inner.stream_options(options))
+    }
+
+    /// Cancel the request or stream when the token is cancelled, for example from another thread
+    pub fn cancellation_token(self, token: CancellationToken) -> Self {
+        self.map(|inner| inner.cancellation_token(token))
+    }
+
+    /// Send the request (non-streaming)
+    ///
+    /// # Errors
+    /// Returns an error if the request fails due to network issues, authentication problems,
+    /// or invalid parameters.
+    pub fn send(self) -> Result<ChatResponse> {
+        self.runtime.block_on(self.inner.send())
+    }
+
+    /// Send the request with streaming
+    ///
+    /// # Errors
+    /// Returns an error if the request fails due to network issues, authentication problems,
+    /// or invalid parameters.
+    pub fn stream(self) -> Result<ChatStream> {
+        let stream = self.runtime.block_on(self.inner.stream())?;
+        Ok(ChatStream::new(stream, self.runtime))
+    }
+}
+
+/// Blocking builder for structured model create requests
+#[derive(Debug, Clone)]
+pub struct CreateModelBuilder {
+    inner: crate::CreateModelBuilder,
+    runtime: Arc<Runtime>,
+}
+
+impl CreateModelBuilder {
+    pub(crate) fn new(inner: crate::CreateModelBuilder, runtime: Arc<Runtime>) -> Self {
+        Self { inner, runtime }
+    }
+
+    fn map(self, f: impl FnOnce(crate::CreateModelBuilder) -> crate::CreateModelBuilder) -> Self {
+        Self { inner: f(self.inner), runtime: self.runtime }
+    }
+
+    /// Set the name of the new model
+    pub fn model<S: Into<String>>(self, model: S) -> Self {
+        self.map(|inner| inner.model(model))
+    }
+
+    /// Build from an existing model
+    pub fn from<S: Into<String>>(self, from: S) -> Self {
+        self.map(|inner| inner.from(from))
+    }
+
+    /// Add a model file already uploaded as a blob
+    pub fn file<N: Into<String>, D: Into<String>>(self, name: N, digest: D) -> Self {
+        self.map(|inner| inner.file(name, digest))
+    }
+
+    /// Add a local model file, uploaded as a blob if the server lacks it
+    pub fn local_file<P: Into<PathBuf>>(self, path: P) -> Self {
+        self.map(|inner| inner.local_file(path))
+    }
+
+    /// Add a LoRA adapter already uploaded as a blob
+    pub fn adapter<N: Into<String>, D: Into<String>>(self, name: N, digest: D) -> Self {
+        self.map(|inner| inner.adapter(name, digest))
+    }
+
+    /// Add a local LoRA adapter, uploaded as a blob if the server lacks it
+    pub fn local_adapter<P: Into<PathBuf>>(self, path: P) -> Self {
+        self.map(|inner| inner.local_adapter(path))
+    }
+
+    /// Set the prompt template
+    pub fn template<S: Into<String>>(self, template: S) -> Self {
+        self.map(|inner| inner.template(template))
+    }
+
+    /// Set the system prompt
+    pub fn system<S: Into<String>>(self, system: S) -> Self {
+        self.map(|inner| inner.system(system))
+    }
+
+    /// Add a license text
+    pub fn license<S: Into<String>>(self, license: S) -> Self {
+        self.map(|inner| inner.license(license))
+    }
+
+    /// Set default generation parameters
+    pub fn parameters(self, parameters: Options) -> Self {
+        self.map(|inner| inner.parameters(parameters))
+    }
+
+    /// Add a message that starts every conversation
+    pub fn add_message(self, message: ChatMessage) -> Self {
+        self.map(|inner| inner.add_message(message))
+    }
+
+    /// Set all starting messages at once
+    pub fn messages(self, messages: Vec<ChatMessage>) -> Self {
+        self.map(|inner| inner.messages(messages))
+    }
+
+    /// Quantize unquantized weights, for example to `q4_K_M`
+    pub fn quantize<S: Into<String>>(self, quantize: S) -> Self {
+        self.map(|inner| inner.quantize(quantize))
+    }
+
+    /// Override the timeout, retries or headers for this request only
+    pub fn request_options(self, options: RequestOptions) -> Self {
+        self.map(|inner| inner.request_options(options))
+    }
+
+    /// Override streaming behavior such as the idle timeout between chunks
+    pub fn stream_options(self, options: StreamOptions) -> Self {
+        self.map(|inner| inner.stream_options(options))
+    }
+
+    /// Cancel the uploads, request or stream when the token is cancelled
+    pub fn cancellation_token(self, token: CancellationToken) -> Self {
+        self.map(|inner| inner.cancellation_token(token))
+    }
+
+    /// Upload local files and send the request (non-streaming)
+    ///
+    /// # Errors
+    /// Returns an error if a local file cannot be read or uploaded, or the
+    /// create request fails.
+    pub fn send(self) -> Result<()> {
+        self.runtime.block_on(self.inner.send())
+    }
+
+    /// Upload local files and send the request, iterating over progress updates
+    ///
+    /// # Errors
+    /// Returns an error if a local file cannot be read or uploaded, or the
+    /// create request fails.
+    pub fn stream(self) -> Result<impl Iterator<Item = Result<CreateProgress>> + use<>> {
+        let stream = self.runtime.block_on(self.inner.stream())?;
+        Ok(BlockingIter::new(Box::pin(stream), self.runtime))
+    }
+}
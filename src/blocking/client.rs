@@ -0,0 +1,396 @@
+//! Blocking client for interacting with the Ollama API
+
+use crate::{
+    blocking::{
+        builders::{ChatBuilder, CreateModelBuilder, GenerateBuilder},
+        stream::BlockingIter,
+    },
+    config::ClientConfig,
+    error::Result,
+    models::{
+        common::{KeepAlive, Options},
+        embedding::{EmbedInput, EmbedResponse},
+        model_info::{
+            CreateProgress, ModelInfo, ModelList, PullProgress, PushProgress, RunningModels,
+        },
+        options::{RequestOptions, StreamOptions},
+    },
+    types::{HealthStatus, ServerCapabilities, VersionInfo},
+    utils::{
+        breaker::CircuitStatus, cancel::CancellationToken, retry::ResilientPullOptions,
+        transport::Transport,
+    },
+};
+use std::sync::Arc;
+use tokio::runtime::{self, Runtime};
+
+/// Blocking client for interacting with the Ollama API
+///
+/// Wraps an async [`OllamaClient`](crate::OllamaClient) and a runtime that
+/// drives it. Cloning is cheap and shares both.
+#[derive(Debug, Clone)]
+pub struct OllamaClient {
+    inner: crate::OllamaClient,
+    runtime: Arc<Runtime>,
+}
+
+impl OllamaClient {
+    /// Create a new Ollama client with the default configuration
+    pub fn new<U: AsRef<str>>(base_url: U) -> Result<Self> {
+        Self::build(|| crate::OllamaClient::new(base_url))
+    }
+
+    /// Create a new Ollama client with custom configuration
+    pub fn with_config(config: ClientConfig) -> Result<Self> {
+        Self::build(|| crate::OllamaClient::with_config(config))
+    }
+
+    /// Create a new Ollama client that sends requests through an existing `reqwest::Client`
+    pub fn with_reqwest_client(config: ClientConfig, client: reqwest::Client) -> Result<Self> {
+        Self::build(|| Ok(crate::OllamaClient::with_reqwest_client(config, client)))
+    }
+
+    /// Create a new Ollama client that sends requests through a custom [`Transport`]
+    pub fn with_transport<T: Transport + 'static>(
+        config: ClientConfig,
+        transport: T,
+    ) -> Result<Self> {
+        Self::build(|| Ok(crate::OllamaClient::with_transport(config, transport)))
+    }
+
+    /// Create a new Ollama client using environment variables
+    ///
+    /// Reads the same variables as [`OllamaClient::from_env`](crate::OllamaClient::from_env).
+    pub fn from_env() -> Result<Self> {
+        Self::build(crate::OllamaClient::from_env)
+    }
+
+    fn build(client: impl FnOnce() -> Result<crate::OllamaClient>) -> Result<Self> {
+        let runtime = runtime::Builder::new_multi_thread()
+            .worker_threads(1)
+            .thread_name("ollama-blocking")
+            .enable_all()
+            .build()?;
+        let inner = {
+            let _guard = runtime.enter();
+            client()?
+        };
+        Ok(Self { inner, runtime: Arc::new(runtime) })
+    }
+
+    /// Get the client configuration
+    pub fn config(&self) -> &ClientConfig {
+        self.inner.config()
+    }
+
+    /// Get the circuit breaker state of every endpoint that has seen a failure
+    pub fn circuit_status(&self) -> Vec<CircuitStatus> {
+        self.inner.circuit_status()
+    }
+
+    /// Create a handle whose requests are cancelled when the token is cancelled
+    ///
+    /// Cancel the token from another thread to interrupt a blocked call or stream.
+    pub fn with_cancellation(&self, token: CancellationToken) -> Self {
+        Self { inner: self.inner.with_cancellation(token), runtime: self.runtime.clone() }
+    }
+
+    /// Create a handle whose requests use per-request overrides
+    pub fn with_request_options(&self, options: RequestOptions) -> Self {
+        Self { inner: self.inner.with_request_options(options), runtime: self.runtime.clone() }
+    }
+
+    /// Create a handle whose streams use streaming overrides
+    pub fn with_stream_options(&self, options: StreamOptions) -> Self {
+        Self { inner: self.inner.with_stream_options(options), runtime: self.runtime.clone() }
+    }
+
+    /// Check if the Ollama server is healthy
+    pub fn health(&self) -> Result<bool> {
+        self.runtime.block_on(self.inner.health())
+    }
+
+    /// Check the Ollama server's health in detail
+    pub fn health_detailed(&self) -> HealthStatus {
+        self.runtime.block_on(self.inner.health_detailed())
+    }
+
+    /// Get the Ollama server version, parsed for comparison
+    pub fn server_version(&self) -> Result<VersionInfo> {
+        self.runtime.block_on(self.inner.server_version())
+    }
+
+    /// Detect which optional features the server supports
+    pub fn capabilities(&self) -> Result<ServerCapabilities> {
+        self.runtime.block_on(self.inner.capabilities())
+    }
+
+    /// Get the Ollama server version
+    pub fn version(&self) -> Result<serde_json::Value> {
+        self.runtime.block_on(self.inner.version())
+    }
+
+    /// Create a generate request builder
+    pub fn generate(&self) -> GenerateBuilder {
+        GenerateBuilder::new(self.inner.generate(), self.runtime.clone())
+    }
+
+    /// Create a chat request builder
+    pub fn chat(&self) -> ChatBuilder {
+        ChatBuilder::new(self.inner.chat(), self.runtime.clone())
+    }
+
+    /// Create an embeddings request builder
+    pub fn embed(&self) -> EmbedRequestBuilder {
+        EmbedRequestBuilder { inner: self.inner.embed(), runtime: self.runtime.clone() }
+    }
+
+    /// List all available models
+    pub fn list_models(&self) -> Result<ModelList> {
+        self.runtime.block_on(self.inner.list_models())
+    }
+
+    /// Get information about a specific model
+    pub fn show_model(&self, name: &str) -> Result<ModelInfo> {
+        self.runtime.block_on(self.inner.show_model(name))
+    }
+
+    /// Pull a model from the registry
+    pub fn pull_model(&self, name: &str) -> Result<()> {
+        self.runtime.block_on(self.inner.pull_model(name))
+    }
+
+    /// Pull a model, iterating over progress updates
+    pub fn pull_model_stream(
+        &self,
+        name: &str,
+    ) -> Result<impl Iterator<Item = Result<PullProgress>> + use<>> {
+        let stream = self.runtime.block_on(self.inner.pull_model_stream(name))?;
+        Ok(BlockingIter::new(Box::pin(stream), self.runtime.clone()))
+    }
+
+    /// Pull a model, reconnecting with backoff when the progress stream fails
+    pub fn pull_model_resilient<O: Into<ResilientPullOptions>>(
+        &self,
+        name: &str,
+        options: O,
+    ) -> impl Iterator<Item = Result<PullProgress>> + use<O> {
+        let stream = self.inner.pull_model_resilient(name, options);
+        BlockingIter::new(Box::pin(stream), self.runtime.clone())
+    }
+
+    /// Push a model to a registry
+    pub fn push_model(&self, name: &str, insecure: bool) -> Result<()> {
+        self.runtime.block_on(self.inner.push_model(name, insecure))
+    }
+
+    /// Push a model, iterating over progress updates
+    pub fn push_model_stream(
+        &self,
+        name: &str,
+        insecure: bool,
+    ) -> Result<impl Iterator<Item = Result<PushProgress>> + use<>> {
+        let stream = self.runtime.block_on(self.inner.push_model_stream(name, insecure))?;
+        Ok(BlockingIter::new(Box::pin(stream), self.runtime.clone()))
+    }
+
+    /// Create a new model from a Modelfile
+    pub fn create_model(&self, name: &str, modelfile: &str) -> Result<()> {
+        self.runtime.block_on(self.inner.create_model(name, modelfile))
+    }
+
+    /// Create a builder for a structured model create request
+    pub fn create(&self) -> CreateModelBuilder {
+        CreateModelBuilder::new(self.inner.create(), self.runtime.clone())
+    }
+
+    /// Create a model, iterating over progress updates
+    pub fn create_model_stream(
+        &self,
+        name: &str,
+        modelfile: &str,
+    ) -> Result<impl Iterator<Item = Result<CreateProgress>> + use<>> {
+        let stream = self.runtime.block_on(self.inner.create_model_stream(name, modelfile))?;
+        Ok(BlockingIter::new(Box::pin(stream), self.runtime.clone()))
+    }
+
+    /// Copy a model
+    pub fn copy_model(&self, source: &str, destination: &str) -> Result<()> {
+        self.runtime.block_on(self.inner.copy_model(source, destination))
+    }
+
+    /// Delete a model
+    pub fn delete_model(&self, name: &str) -> Result<()> {
+        self.runtime.block_on(self.inner.delete_model(name))
+    }
+
+    /// List currently running models
+    pub fn list_running_models(&self) -> Result<RunningModels> {
+        self.runtime.block_on(self.inner.list_running_models())
+    }
+
+    /// Check if a blob exists
+    pub fn blob_exists(&self, digest: &str) -> Result<bool> {
+        self.runtime.block_on(self.inner.blob_exists(digest))
+    }
+
+    /// Create/upload a blob
+    pub fn create_blob<B: Into<reqwest::Body>>(&self, digest: &str, data: B) -> Result<()> {
+        self.runtime.block_on(self.inner.create_blob(digest, data))
+    }
+}
+
+/// Blocking builder for embedding requests
+#[derive(Debug)]
+pub struct EmbedRequestBuilder {
+    inner: crate::client::EmbedRequestBuilder,
+    runtime: Arc<Runtime>,
+}
+
+impl EmbedRequestBuilder {
+    fn map(
+        self,
+        f: impl FnOnce(crate::client::EmbedRequestBuilder) -> crate::client::EmbedRequestBuilder,
+    ) -> Self {
+        Self { inner: f(self.inner), runtime: self.runtime }
+    }
+
+    /// Set the model to use for embeddings
+    pub fn model<S: Into<String>>(self, model: S) -> Self {
+        self.map(|inner| inner.model(model))
+    }
+
+    /// Set the input text(s) to embed
+    pub fn input<I: Into<EmbedInput>>(self, input: I) -> Self {
+        self.map(|inner| inner.input(input))
+    }
+
+    /// Set additional options
+    pub fn options(self, options: Options) -> Self {
+        self.map(|inner| inner.options(options))
+    }
+
+    /// Set keep alive duration
+    pub fn keep_alive(self, keep_alive: KeepAlive) -> Self {
+        self.map(|inner| inner.keep_alive(keep_alive))
+    }
+
+    /// Enable/disable truncation
+    pub fn truncate(self, truncate: bool) -> Self {
+        self.map(|inner| inner.truncate(truncate))
+    }
+
+    /// Override the timeout, retries or headers for this request only
+    pub fn request_options(self, options: RequestOptions) -> Self {
+        self.map(|inner| inner.request_options(options))
+    }
+
+    /// Cancel the request when the token is cancelled
+    pub fn cancellation_token(self, token: CancellationToken) -> Self {
+        self.map(|inner| inner.cancellation_token(token))
+    }
+
+    /// Send the embedding request
+    pub fn send(self) -> Result<EmbedResponse> {
+        self.runtime.block_on(self.inner.send())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::OllamaError;
+    use serde_json::json;
+    use wiremock::{
+        Mock, MockServer, ResponseTemplate,
+        matchers::{method, path},
+    };
+
+    /// Start a mock server on its own runtime, since blocking clients may not run inside one
+    fn mock_server(mocks: Vec<Mock>) -> (Runtime, MockServer) {
+        let runtime = Runtime::new().unwrap();
+        let server = runtime.block_on(async {
+            let server = MockServer::start().await;
+            for mock in mocks {
+                mock.mount(&server).await;
+            }
+            server
+        });
+        (runtime, server)
+    }
+
+    #[test]
+    fn test_blocking_requests() {
+        let (_runtime, server) = mock_server(vec![
+            Mock::given(method("GET"))
+                .and(path("/api/tags"))
+                .respond_with(ResponseTemplate::new(200).set_body_json(json!({"models": []}))),
+            Mock::given(method("POST")).and(path("/api/embed")).respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(json!({"model": "m", "embeddings": [[0.1, 0.2]]})),
+            ),
+            Mock::given(method("POST"))
+                .and(path("/api/show"))
+                .respond_with(ResponseTemplate::new(404).set_body_string("model not found")),
+        ]);
+
+        let client = OllamaClient::new(server.uri()).unwrap();
+        assert!(client.list_models().unwrap().models.is_empty());
+
+        let embedding = client.embed().model("m").input("hello").send().unwrap();
+        assert_eq!(embedding.dimensions(), Some(2));
+
+        assert!(client.clone().show_model("missing").is_err());
+    }
+
+    #[test]
+    fn test_blocking_streams() {
+        let records = [
+            json!({"model": "m", "response": "Hel", "done": false}),
+            json!({"model": "m", "response": "lo", "done": true}),
+        ]
+        .map(|record| format!("{record}\n"))
+        .concat();
+        let (_runtime, server) = mock_server(vec![
+            Mock::given(method("POST"))
+                .and(path("/api/generate"))
+                .respond_with(ResponseTemplate::new(200).set_body_string(records)),
+        ]);
+
+        let client = OllamaClient::new(server.uri()).unwrap();
+        let chunks: Vec<_> = client
+            .generate()
+            .model("m")
+            .prompt("hi")
+            .stream()
+            .unwrap()
+            .map(|chunk| chunk.unwrap().response)
+            .collect();
+        assert_eq!(chunks, ["Hel", "lo"]);
+
+        // Streams keep the runtime alive after the client is dropped
+        let stream = client.generate().model("m").stream().unwrap();
+        drop(client);
+        assert_eq!(stream.collect_response().unwrap().response, "Hello");
+    }
+
+    #[test]
+    fn test_blocking_cancellation() {
+        let (_runtime, server) =
+            mock_server(vec![Mock::given(method("POST")).and(path("/api/chat")).respond_with(
+                ResponseTemplate::new(200).set_delay(std::time::Duration::from_secs(10)),
+            )]);
+
+        let token = CancellationToken::new();
+        let canceller = token.clone();
+        std::thread::spawn(move || {
+            std::thread::sleep(std::time::Duration::from_millis(50));
+            canceller.cancel();
+        });
+
+        let client = OllamaClient::new(server.uri()).unwrap();
+        let result =
+            client.chat().model("m").add_user_message("hi").cancellation_token(token).send();
+        assert!(matches!(result, Err(OllamaError::Cancelled(_))));
+    }
+}
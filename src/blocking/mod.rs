@@ -0,0 +1,29 @@
+//! Blocking client for synchronous code
+//!
+//! The types in this module mirror their async counterparts but wait for each
+//! request to finish on the calling thread, and expose streams as iterators.
+//! Requires the `blocking` feature.
+//!
+//! ```rust,no_run
+//! use ollama_rust_sdk::blocking::OllamaClient;
+//!
+//! let client = OllamaClient::from_env()?;
+//! let mut stream = client.generate().model("llama3.2").prompt("Why is the sky blue?").stream()?;
+//! for chunk in &mut stream {
+//!     print!("{}", chunk?.response);
+//! }
+//! # Ok::<(), ollama_rust_sdk::OllamaError>(())
+//! ```
+//!
+//! Each client owns a small Tokio runtime that drives its requests, shared by
+//! its clones and the builders and streams it returns. Do not create, use, or
+//! drop a blocking client from inside an async runtime; use the async
+//! [`OllamaClient`](crate::OllamaClient) there instead.
+
+mod builders;
+mod client;
+mod stream;
+
+pub use builders::{ChatBuilder, CreateModelBuilder, GenerateBuilder};
+pub use client::{EmbedRequestBuilder, OllamaClient};
+pub use stream::{ChatStream, GenerateStream};
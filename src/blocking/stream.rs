@@ -0,0 +1,80 @@
+//! Blocking iterators over streaming responses
+
+use crate::{
+    error::Result,
+    models::{chat::ChatResponse, generation::GenerateResponse},
+    streaming::stream::StreamChunk,
+};
+use futures_util::{Stream, StreamExt};
+use std::sync::Arc;
+use tokio::runtime::Runtime;
+
+/// Iterator that waits for each item of an async stream on the calling thread
+pub(crate) struct BlockingIter<S> {
+    stream: S,
+    runtime: Arc<Runtime>,
+}
+
+impl<S> BlockingIter<S> {
+    pub(crate) fn new(stream: S, runtime: Arc<Runtime>) -> Self {
+        Self { stream, runtime }
+    }
+}
+
+impl<S: Stream + Unpin> Iterator for BlockingIter<S> {
+    type Item = S::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.runtime.block_on(self.stream.next())
+    }
+}
+
+/// Blocking generate response stream
+pub struct GenerateStream {
+    inner: BlockingIter<crate::GenerateStream>,
+}
+
+impl GenerateStream {
+    pub(crate) fn new(stream: crate::GenerateStream, runtime: Arc<Runtime>) -> Self {
+        Self { inner: BlockingIter::new(stream, runtime) }
+    }
+
+    /// Collect all responses into a single response
+    pub fn collect_response(self) -> Result<GenerateResponse> {
+        let BlockingIter { stream, runtime } = self.inner;
+        runtime.block_on(stream.collect_response())
+    }
+}
+
+impl Iterator for GenerateStream {
+    type Item = StreamChunk<GenerateResponse>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next()
+    }
+}
+
+/// Blocking chat response stream
+pub struct ChatStream {
+    inner: BlockingIter<crate::ChatStream>,
+}
+
+impl ChatStream {
+    pub(crate) fn new(stream: crate::ChatStream, runtime: Arc<Runtime>) -> Self {
+        Self { inner: BlockingIter::new(stream, runtime) }
+    }
+
+    /// Collect all responses into a single response
+    pub fn collect_response(self) -> Result<ChatResponse> {
+        let BlockingIter { stream, runtime } = self.inner;
+        runtime.block_on(stream.collect_response())
+    }
+}
+
+impl Iterator for ChatStream {
+    type Item = StreamChunk<ChatResponse>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next()
+    }
+}
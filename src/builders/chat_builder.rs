@@ -6,9 +6,10 @@ use crate::{
     models::{
         chat::{ChatMessage, ChatRequest, ChatResponse, ToolChoice},
         common::{KeepAlive, Options, ResponseFormat, Tool},
+        options::{RequestOptions, StreamOptions},
     },
     streaming::stream::ChatStream,
-    utils::http::HttpClient,
+    utils::{cancel::CancellationToken, http::HttpClient},
 };
 use std::sync::Arc;
 
@@ -137,6 +138,33 @@ impl ChatBuilder {
         self
     }
 
+    /// Ask a reasoning model to return its thinking in `message.thinking`
+    pub fn think(mut self, think: bool) -> Self {
+        self.request.think = Some(think);
+        self
+    }
+
+    /// Override the timeout, retries or headers for this request only
+    pub fn request_options(mut self, options: RequestOptions) -> Self {
+        self.http_client = Arc::new(self.http_client.with_request_options(&options));
+        self
+    }
+
+    /// Override streaming behavior such as the idle timeout between chunks
+    pub fn stream_options(mut self, options: StreamOptions) -> Self {
+        self.http_client = Arc::new(self.http_client.with_stream_options(&options));
+        self
+    }
+
+    /// Cancel the request or stream when the token is cancelled
+    ///
+    /// Cancellation closes the HTTP connection so the server stops generating, and
+    /// the call or stream yields [`OllamaError::Cancelled`](crate::OllamaError::Cancelled).
+    pub fn cancellation_token(mut self, token: CancellationToken) -> Self {
+        self.http_client = Arc::new(self.http_client.with_cancellation(token));
+        self
+    }
+
     /// Send the request (non-streaming)
     ///
     /// # Errors
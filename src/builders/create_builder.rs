@@ -0,0 +1,305 @@
+//! Builder for structured model create requests
+
+use crate::{
+    api::{blobs::BlobsApi, models::ModelsApi},
+    error::{OllamaError, Result},
+    models::{
+        chat::ChatMessage,
+        common::Options,
+        model_info::{CreateModelRequest, CreateProgress},
+        model_name::ModelName,
+        options::{RequestOptions, StreamOptions},
+    },
+    utils::{cancel::CancellationToken, http::HttpClient},
+};
+use std::{path::PathBuf, sync::Arc};
+
+/// A local file to upload before the create request is sent
+#[derive(Debug, Clone)]
+struct LocalFile {
+    path: PathBuf,
+    adapter: bool,
+}
+
+/// Builder for structured model create requests
+///
+/// Files added with [`local_file`](Self::local_file) or
+/// [`local_adapter`](Self::local_adapter) are hashed and uploaded as blobs,
+/// skipping any the server already has, before the request is sent.
+#[derive(Debug, Clone)]
+pub struct CreateModelBuilder {
+    http_client: Arc<HttpClient>,
+    request: CreateModelRequest,
+    local_files: Vec<LocalFile>,
+}
+
+impl CreateModelBuilder {
+    /// Create a new create builder
+    #[must_use]
+    pub fn new(http_client: Arc<HttpClient>) -> Self {
+        Self { http_client, request: CreateModelRequest::default(), local_files: Vec::new() }
+    }
+
+    /// Set the name of the new model
+    #[must_use]
+    pub fn model<S: Into<String>>(mut self, model: S) -> Self {
+        self.request.model = model.into();
+        self
+    }
+
+    /// Build from an existing model
+    pub fn from<S: Into<String>>(mut self, from: S) -> Self {
+        self.request.from = Some(from.into());
+        self
+    }
+
+    /// Add a model file already uploaded as a blob
+    pub fn file<N: Into<String>, D: Into<String>>(mut self, name: N, digest: D) -> Self {
+        self.request.files.insert(name.into(), digest.into());
+        self
+    }
+
+    /// Add a local model file, uploaded as a blob if the server lacks it
+    pub fn local_file<P: Into<PathBuf>>(mut self, path: P) -> Self {
+        self.local_files.push(LocalFile { path: path.into(), adapter: false });
+        self
+    }
+
+    /// Add a LoRA adapter already uploaded as a blob
+    pub fn adapter<N: Into<String>, D: Into<String>>(mut self, name: N, digest: D) -> Self {
+        self.request.adapters.insert(name.into(), digest.into());
+        self
+    }
+
+    /// Add a local LoRA adapter, uploaded as a blob if the server lacks it
+    pub fn local_adapter<P: Into<PathBuf>>(mut self, path: P) -> Self {
+        self.local_files.push(LocalFile { path: path.into(), adapter: true });
+        self
+    }
+
+    /// Set the prompt template
+    pub fn template<S: Into<String>>(mut self, template: S) -> Self {
+        self.request.template = Some(template.into());
+        self
+    }
+
+    /// Set the system prompt
+    pub fn system<S: Into<String>>(mut self, system: S) -> Self {
+        self.request.system = Some(system.into());
+        self
+    }
+
+    /// Add a license text
+    pub fn license<S: Into<String>>(mut self, license: S) -> Self {
+        self.request.license.push(license.into());
+        self
+    }
+
+    /// Set default generation parameters
+    pub fn parameters(mut self, parameters: Options) -> Self {
+        self.request.parameters = Some(parameters);
+        self
+    }
+
+    /// Add a message that starts every conversation
+    pub fn add_message(mut self, message: ChatMessage) -> Self {
+        self.request.messages.push(message);
+        self
+    }
+
+    /// Set all starting messages at once
+    pub fn messages(mut self, messages: Vec<ChatMessage>) -> Self {
+        self.request.messages = messages;
+        self
+    }
+
+    /// Quantize unquantized weights, for example to `q4_K_M`
+    pub fn quantize<S: Into<String>>(mut self, quantize: S) -> Self {
+        self.request.quantize = Some(quantize.into());
+        self
+    }
+
+    /// Override the timeout, retries or headers for this request only
+    pub fn request_options(mut self, options: RequestOptions) -> Self {
+        self.http_client = Arc::new(self.http_client.with_request_options(&options));
+        self
+    }
+
+    /// Override streaming behavior such as the idle timeout between chunks
+    pub fn stream_options(mut self, options: StreamOptions) -> Self {
+        self.http_client = Arc::new(self.http_client.with_stream_options(&options));
+        self
+    }
+
+    /// Cancel the uploads, request or stream when the token is cancelled
+    pub fn cancellation_token(mut self, token: CancellationToken) -> Self {
+        self.http_client = Arc::new(self.http_client.with_cancellation(token));
+        self
+    }
+
+    /// Upload local files and send the request (non-streaming)
+    ///
+    /// # Errors
+    /// Returns an error if a local file cannot be read or uploaded, or the
+    /// create request fails.
+    pub async fn send(self) -> Result<()> {
+        let request = self.resolve_files().await?;
+        ModelsApi::create_model_structured(&self.http_client, request).await
+    }
+
+    /// Upload local files and send the request with streaming progress
+    ///
+    /// # Errors
+    /// Returns an error if a local file cannot be read or uploaded, or the
+    /// create request fails.
+    pub async fn stream(
+        self,
+    ) -> Result<impl tokio_stream::Stream<Item = Result<CreateProgress>> + use<>> {
+        let request = self.resolve_files().await?;
+        ModelsApi::create_model_structured_stream(&self.http_client, request).await
+    }
+
+    /// Upload every local file and add its digest to the request
+    async fn resolve_files(&self) -> Result<CreateModelRequest> {
+        // Reject an invalid name before uploading anything
+        ModelName::parse(&self.request.model)?;
+        let mut request = self.request.clone();
+        for file in &self.local_files {
+            let Some(name) = file.path.file_name().and_then(|name| name.to_str()) else {
+                return Err(OllamaError::InvalidParameter {
+                    parameter: if file.adapter { "adapters" } else { "files" }.to_string(),
+                    reason: format!("{} has no file name", file.path.display()),
+                });
+            };
+            let digest = BlobsApi::upload_file(&self.http_client, &file.path).await?;
+            let files = if file.adapter { &mut request.adapters } else { &mut request.files };
+            files.insert(name.to_string(), digest);
+        }
+        Ok(request)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ClientConfig;
+    use futures_util::StreamExt;
+    use serde_json::json;
+    use wiremock::{
+        Mock, MockServer, ResponseTemplate,
+        matchers::{body_json, method, path},
+    };
+
+    fn client_for(mock_server: &MockServer) -> Arc<HttpClient> {
+        Arc::new(HttpClient::new(ClientConfig::new(mock_server.uri()).unwrap()).unwrap())
+    }
+
+    #[test]
+    fn test_request_serialization() {
+        let config = ClientConfig::default();
+        let builder = CreateModelBuilder::new(Arc::new(HttpClient::new(config).unwrap()))
+            .model("mario")
+            .from("llama3.2")
+            .system("You are Mario.")
+            .parameters(Options { temperature: Some(0.7), ..Options::default() })
+            .add_message(ChatMessage::user("Who are you?"))
+            .license("MIT");
+
+        assert_eq!(
+            serde_json::to_value(&builder.request).unwrap(),
+            json!({
+                "model": "mario",
+                "from": "llama3.2",
+                "license": ["MIT"],
+                "system": "You are Mario.",
+                "parameters": {"temperature": 0.7},
+                "messages": [{"role": "user", "content": "Who are you?"}]
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_uploads_missing_blobs_before_create() {
+        let mock_server = MockServer::start().await;
+        let dir = tempfile::tempdir().unwrap();
+        let weights = dir.path().join("model.gguf");
+        std::fs::write(&weights, b"weights").unwrap();
+        let adapter = dir.path().join("adapter.gguf");
+        std::fs::write(&adapter, b"adapter").unwrap();
+        let weights_digest = BlobsApi::digest(b"weights");
+        let adapter_digest = BlobsApi::digest(b"adapter");
+
+        Mock::given(method("HEAD"))
+            .and(path(format!("/api/blobs/{weights_digest}")))
+            .respond_with(ResponseTemplate::new(404))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("PUT"))
+            .and(path(format!("/api/blobs/{weights_digest}")))
+            .respond_with(ResponseTemplate::new(201))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("HEAD"))
+            .and(path(format!("/api/blobs/{adapter_digest}")))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("PUT"))
+            .and(path(format!("/api/blobs/{adapter_digest}")))
+            .respond_with(ResponseTemplate::new(201))
+            .expect(0)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/api/create"))
+            .and(body_json(json!({
+                "model": "custom",
+                "files": {"model.gguf": weights_digest},
+                "adapters": {"adapter.gguf": adapter_digest},
+                "stream": true,
+                "quantize": "q4_K_M"
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_string(concat!(
+                "{\"status\":\"quantizing F16 model to Q4_K_M\"}\n",
+                "{\"status\":\"success\"}\n"
+            )))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let stream = CreateModelBuilder::new(client_for(&mock_server))
+            .model("custom")
+            .local_file(&weights)
+            .local_adapter(&adapter)
+            .quantize("q4_K_M")
+            .stream()
+            .await
+            .unwrap();
+        let statuses: Vec<_> = stream.map(|progress| progress.unwrap().status).collect().await;
+
+        assert_eq!(statuses.last().map(String::as_str), Some("success"));
+    }
+
+    #[tokio::test]
+    async fn test_missing_local_file_fails_before_create() {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/api/create"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(0)
+            .mount(&mock_server)
+            .await;
+
+        let error = CreateModelBuilder::new(client_for(&mock_server))
+            .model("custom")
+            .local_file("/nonexistent/model.gguf")
+            .send()
+            .await
+            .unwrap_err();
+
+        assert!(matches!(error, OllamaError::IoError(_)));
+    }
+}
@@ -6,9 +6,10 @@ use crate::{
     models::{
         common::{KeepAlive, Options, ResponseFormat},
         generation::{GenerateRequest, GenerateResponse},
+        options::{RequestOptions, StreamOptions},
     },
     streaming::stream::GenerateStream,
-    utils::http::HttpClient,
+    utils::{cancel::CancellationToken, http::HttpClient},
 };
 use std::sync::Arc;
 
@@ -118,6 +119,33 @@ impl GenerateBuilder {
         self
     }
 
+    /// Ask a reasoning model to return its thinking in `thinking`
+    pub fn think(mut self, think: bool) -> Self {
+        self.request.think = Some(think);
+        self
+    }
+
+    /// Override the timeout, retries or headers for this request only
+    pub fn request_options(mut self, options: RequestOptions) -> Self {
+        self.http_client = Arc::new(self.http_client.with_request_options(&options));
+        self
+    }
+
+    /// Override streaming behavior such as the idle timeout between chunks
+    pub fn stream_options(mut self, options: StreamOptions) -> Self {
+        self.http_client = Arc::new(self.http_client.with_stream_options(&options));
+        self
+    }
+
+    /// Cancel the request or stream when the token is cancelled
+    ///
+    /// Cancellation closes the HTTP connection so the server stops generating, and
+    /// the call or stream yields [`OllamaError::Cancelled`](crate::OllamaError::Cancelled).
+    pub fn cancellation_token(mut self, token: CancellationToken) -> Self {
+        self.http_client = Arc::new(self.http_client.with_cancellation(token));
+        self
+    }
+
     /// Send the request (non-streaming)
     pub async fn send(self) -> Result<GenerateResponse> {
         GenerateApi::generate(&self.http_client, self.request).await
@@ -133,6 +161,13 @@ impl GenerateBuilder {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::error::OllamaError;
+    use std::{
+        io::{Read, Write},
+        net::TcpListener,
+        time::Duration,
+    };
+    use tokio_stream::StreamExt;
 
     #[test]
     fn test_generate_builder() {
@@ -153,4 +188,192 @@ mod tests {
         assert_eq!(options.temperature, Some(0.7));
         assert_eq!(options.num_predict, Some(100));
     }
+
+    /// Serve one streaming response that sends a single record and then stalls,
+    /// signalling once the client closes the connection.
+    fn stalled_stream_server() -> (String, tokio::sync::oneshot::Receiver<()>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        let (closed_tx, closed_rx) = tokio::sync::oneshot::channel();
+
+        std::thread::spawn(move || {
+            let (mut socket, _) = listener.accept().unwrap();
+            let mut buffer = [0u8; 4096];
+            let _ = socket.read(&mut buffer);
+
+            let record = "{\"model\":\"m\",\"response\":\"Hi\",\"done\":false}\n";
+            write!(
+                socket,
+                "HTTP/1.1 200 OK\r\nContent-Type: application/x-ndjson\r\n\
+                 Transfer-Encoding: chunked\r\n\r\n{:x}\r\n{record}\r\n",
+                record.len()
+            )
+            .unwrap();
+            socket.flush().unwrap();
+
+            while let Ok(read) = socket.read(&mut buffer) {
+                if read == 0 {
+                    break;
+                }
+            }
+            let _ = closed_tx.send(());
+        });
+
+        (format!("http://{address}"), closed_rx)
+    }
+
+    #[tokio::test]
+    async fn test_cancel_stream_closes_connection() {
+        let (uri, closed) = stalled_stream_server();
+        let config = crate::config::ClientConfig::new(uri).unwrap();
+        let http_client = Arc::new(HttpClient::new(config).unwrap());
+        let token = CancellationToken::new();
+
+        let mut stream = GenerateBuilder::new(http_client)
+            .model("m")
+            .prompt("hi")
+            .cancellation_token(token.clone())
+            .stream()
+            .await
+            .unwrap();
+
+        let first = stream.next().await.unwrap().unwrap();
+        assert_eq!(first.response, "Hi");
+
+        token.cancel();
+        assert!(matches!(stream.next().await.transpose(), Err(OllamaError::Cancelled(_))));
+        assert!(stream.next().await.is_none());
+
+        tokio::time::timeout(Duration::from_secs(5), closed)
+            .await
+            .expect("connection should be closed after cancellation")
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_cancelled_token_fails_before_sending() {
+        let config = crate::config::ClientConfig::default();
+        let http_client = Arc::new(HttpClient::new(config).unwrap());
+        let token = CancellationToken::new();
+        token.cancel();
+
+        let result =
+            GenerateBuilder::new(http_client).model("m").cancellation_token(token).send().await;
+
+        assert!(matches!(result, Err(OllamaError::Cancelled(_))));
+    }
+
+    #[tokio::test]
+    async fn test_stream_idle_timeout_closes_connection() {
+        let (uri, closed) = stalled_stream_server();
+        let config = crate::config::ClientConfig::new(uri).unwrap();
+        let http_client = Arc::new(HttpClient::new(config).unwrap());
+
+        let mut stream = GenerateBuilder::new(http_client)
+            .model("m")
+            .prompt("hi")
+            .stream_options(StreamOptions { chunk_timeout: Some(1), ..Default::default() })
+            .stream()
+            .await
+            .unwrap();
+
+        assert_eq!(stream.next().await.unwrap().unwrap().response, "Hi");
+        assert!(
+            matches!(stream.next().await.transpose(), Err(OllamaError::StreamIdleTimeout(d, _)) if d == Duration::from_secs(1)
+            )
+        );
+        assert!(stream.next().await.is_none());
+        tokio::time::timeout(Duration::from_secs(5), closed).await.unwrap().unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_slow_record_resets_idle_timeout_per_chunk() {
+        // After a first record, one record sent in five pieces 300ms apart, which
+        // takes longer overall than the idle timeout
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        std::thread::spawn(move || {
+            let (mut socket, _) = listener.accept().unwrap();
+            let mut buffer = [0u8; 4096];
+            let _ = socket.read(&mut buffer);
+
+            let first = "{\"model\":\"m\",\"response\":\"Hi\",\"done\":false}\n";
+            let record =
+                format!("{{\"model\":\"m\",\"response\":\"{}\",\"done\":true}}\n", "x".repeat(95));
+            write!(
+                socket,
+                "HTTP/1.1 200 OK\r\nContent-Type: application/x-ndjson\r\n\
+                 Transfer-Encoding: chunked\r\n\r\n{:x}\r\n{first}\r\n",
+                first.len()
+            )
+            .unwrap();
+            for piece in record.as_bytes().chunks(record.len().div_ceil(5)) {
+                std::thread::sleep(Duration::from_millis(300));
+                write!(socket, "{:x}\r\n", piece.len()).unwrap();
+                socket.write_all(piece).unwrap();
+                write!(socket, "\r\n").unwrap();
+                socket.flush().unwrap();
+            }
+            let _ = write!(socket, "0\r\n\r\n");
+        });
+
+        let config = crate::config::ClientConfig::builder()
+            .base_url(format!("http://{address}"))
+            .chunk_timeout(Duration::from_secs(1))
+            .build()
+            .unwrap();
+        let http_client = Arc::new(HttpClient::new(config).unwrap());
+
+        let mut stream =
+            GenerateBuilder::new(http_client).model("m").prompt("hi").stream().await.unwrap();
+
+        assert_eq!(stream.next().await.unwrap().unwrap().response, "Hi");
+        let record = stream.next().await.unwrap().unwrap();
+        assert_eq!(record.response.len(), 95);
+        assert!(stream.next().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_stream_outlives_whole_request_timeout() {
+        let (uri, _closed) = stalled_stream_server();
+        let config = crate::config::ClientConfig {
+            base_url: uri.parse().unwrap(),
+            timeout: Duration::from_millis(200),
+            chunk_timeout: Some(Duration::from_secs(1)),
+            ..Default::default()
+        };
+        let http_client = Arc::new(HttpClient::new(config).unwrap());
+
+        let mut stream =
+            GenerateBuilder::new(http_client).model("m").prompt("hi").stream().await.unwrap();
+
+        assert_eq!(stream.next().await.unwrap().unwrap().response, "Hi");
+        // The stall is reported by the idle timeout, not the 200ms request timeout
+        assert!(matches!(stream.next().await.transpose(), Err(OllamaError::StreamIdleTimeout(..))));
+    }
+
+    #[tokio::test]
+    async fn test_stream_first_chunk_timeout() {
+        use wiremock::{Mock, MockServer, ResponseTemplate, matchers::path};
+
+        let mock_server = MockServer::start().await;
+        Mock::given(path("/api/generate"))
+            .respond_with(ResponseTemplate::new(200).set_delay(Duration::from_secs(5)))
+            .mount(&mock_server)
+            .await;
+
+        let config = crate::config::ClientConfig::builder()
+            .base_url(mock_server.uri())
+            .first_chunk_timeout(Duration::from_millis(200))
+            .build()
+            .unwrap();
+        let http_client = Arc::new(HttpClient::new(config).unwrap());
+
+        let result = GenerateBuilder::new(http_client).model("m").prompt("hi").stream().await;
+
+        assert!(
+            matches!(result, Err(OllamaError::FirstChunkTimeout(d, _)) if d == Duration::from_millis(200)
+            )
+        );
+    }
 }
@@ -1,4 +1,5 @@
 //! Builder patterns for API requests
 
 pub mod chat_builder;
+pub mod create_builder;
 pub mod generate_builder;
@@ -1,18 +1,40 @@
 //! Main client for interacting with the Ollama API
 
 use crate::{
-    api::{blobs::BlobsApi, embeddings::EmbeddingsApi, models::ModelsApi},
-    builders::{chat_builder::ChatBuilder, generate_builder::GenerateBuilder},
+    api::{
+        blobs::BlobsApi, capabilities::CapabilitiesApi, embeddings::EmbeddingsApi,
+        models::ModelsApi,
+    },
+    builders::{
+        chat_builder::ChatBuilder, create_builder::CreateModelBuilder,
+        generate_builder::GenerateBuilder,
+    },
     config::ClientConfig,
     error::{OllamaError, Result},
     models::{
         embedding::EmbedRequest,
-        model_info::{ModelInfo, ModelList, RunningModels},
+        model_info::{
+            CreateProgress, ModelInfo, ModelList, PullProgress, PushProgress, RunningModels,
+        },
+        options::{RequestOptions, StreamOptions},
+    },
+    types::{HealthStatus, ServerCapabilities, VersionInfo},
+    utils::{
+        auth::EnvApiKey,
+        breaker::CircuitStatus,
+        cancel::CancellationToken,
+        http::HttpClient,
+        retry::{ResilientPullOptions, RetryPolicy},
+        transport::Transport,
     },
-    utils::http::HttpClient,
 };
+use chrono::Utc;
 use serde_json::Value;
-use std::{env, sync::Arc, time::Duration};
+use std::{
+    env,
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
 /// Main client for interacting with the Ollama API
 #[derive(Debug, Clone)]
@@ -37,6 +59,25 @@ impl OllamaClient {
         Ok(Self { http_client: Arc::new(http_client), config: Arc::new(config) })
     }
 
+    /// Create a new Ollama client that sends requests through an existing `reqwest::Client`
+    ///
+    /// Use this to share a connection pool with the rest of an application. The
+    /// client's own connect timeout, user agent and redirect policy apply instead
+    /// of the corresponding `config` fields.
+    pub fn with_reqwest_client(config: ClientConfig, client: reqwest::Client) -> Self {
+        Self::with_transport(config, client)
+    }
+
+    /// Create a new Ollama client that sends requests through a custom [`Transport`]
+    ///
+    /// Retries, cancellation and response decoding still happen in the SDK; the
+    /// transport only performs individual HTTP exchanges.
+    pub fn with_transport<T: Transport + 'static>(config: ClientConfig, transport: T) -> Self {
+        let http_client = HttpClient::with_transport(config.clone(), Arc::new(transport));
+
+        Self { http_client: Arc::new(http_client), config: Arc::new(config) }
+    }
+
     /// Create a new Ollama client using environment variables.
     ///
     /// Supported variables:
@@ -44,6 +85,9 @@ impl OllamaClient {
     /// - `OLLAMA_TIMEOUT_SECS`
     /// - `OLLAMA_USER_AGENT`
     /// - `OLLAMA_API_HEADERS` (JSON object of header key/value pairs)
+    /// - `OLLAMA_API_KEY` (bearer token, re-read before every request)
+    /// - `OLLAMA_PROXY` and `OLLAMA_NO_PROXY`
+    /// - `OLLAMA_CA_BUNDLE`, `OLLAMA_CLIENT_CERT` and `OLLAMA_CLIENT_KEY` (with the `tls` feature)
     pub fn from_env() -> Result<Self> {
         let base_url =
             env::var("OLLAMA_BASE_URL").unwrap_or_else(|_| "http://127.0.0.1:11434".to_string());
@@ -70,6 +114,33 @@ impl OllamaClient {
             }
         }
 
+        if non_empty_env(EnvApiKey::DEFAULT_VAR).is_some() {
+            builder = builder.credentials(EnvApiKey::new());
+        }
+
+        if let Some(proxy) = non_empty_env("OLLAMA_PROXY") {
+            builder = builder.proxy(proxy);
+        }
+        if let Some(no_proxy) = non_empty_env("OLLAMA_NO_PROXY") {
+            builder = builder.no_proxy(no_proxy);
+        }
+
+        #[cfg(feature = "tls")]
+        {
+            if let Some(ca_bundle) = non_empty_env("OLLAMA_CA_BUNDLE") {
+                builder = builder.ca_bundle(ca_bundle);
+            }
+            match (non_empty_env("OLLAMA_CLIENT_CERT"), non_empty_env("OLLAMA_CLIENT_KEY")) {
+                (Some(cert), key) => builder = builder.client_identity(cert, key),
+                (None, Some(_)) => {
+                    return Err(OllamaError::ConfigError(
+                        "OLLAMA_CLIENT_KEY requires OLLAMA_CLIENT_CERT".to_string(),
+                    ));
+                }
+                (None, None) => {}
+            }
+        }
+
         let config = builder.build()?;
         Self::with_config(config)
     }
@@ -79,19 +150,132 @@ impl OllamaClient {
         &self.config
     }
 
+    /// Get the circuit breaker state of every endpoint that has seen a failure
+    ///
+    /// Empty unless a circuit breaker is configured with
+    /// [`ClientConfigBuilder::circuit_breaker`](crate::ClientConfigBuilder::circuit_breaker).
+    pub fn circuit_status(&self) -> Vec<CircuitStatus> {
+        self.http_client.circuit_status()
+    }
+
+    pub(crate) fn http_client(&self) -> &Arc<HttpClient> {
+        &self.http_client
+    }
+
+    /// Create a handle whose requests are cancelled when the token is cancelled
+    ///
+    /// The returned client shares configuration and connections with `self`. Every
+    /// operation started through it, including model pulls, creates and the streams
+    /// they return, stops with [`OllamaError::Cancelled`] and closes its connection
+    /// once the token fires.
+    pub fn with_cancellation(&self, token: CancellationToken) -> Self {
+        Self {
+            http_client: Arc::new(self.http_client.with_cancellation(token)),
+            config: self.config.clone(),
+        }
+    }
+
+    /// Create a handle whose requests use per-request overrides
+    ///
+    /// Use this to give model operations such as pulls a different timeout, retry
+    /// count or extra headers without building a second client. The returned
+    /// client shares configuration and connections with `self`.
+    pub fn with_request_options(&self, options: RequestOptions) -> Self {
+        Self {
+            http_client: Arc::new(self.http_client.with_request_options(&options)),
+            config: self.config.clone(),
+        }
+    }
+
+    /// Create a handle whose streams use streaming overrides
+    ///
+    /// Use this to give pull and create progress streams a different idle
+    /// timeout between chunks. The returned client shares configuration and
+    /// connections with `self`.
+    pub fn with_stream_options(&self, options: StreamOptions) -> Self {
+        Self {
+            http_client: Arc::new(self.http_client.with_stream_options(&options)),
+            config: self.config.clone(),
+        }
+    }
+
     /// Check if the Ollama server is healthy
+    ///
+    /// Health probes report the server's current state and are never retried.
     pub async fn health(&self) -> Result<bool> {
-        match self.http_client.get("").await {
+        match self.http_client.with_retry_policy(RetryPolicy::none()).get("").await {
             Ok(response) => Ok(response.status().is_success()),
             Err(_) => Ok(false),
         }
     }
 
+    /// Check the Ollama server's health in detail
+    ///
+    /// Measures the latency of the health request and, when it succeeds, reads
+    /// the server version and the number of loaded models. Failures are reported
+    /// in [`HealthStatus::error`] rather than returned, and nothing is retried.
+    pub async fn health_detailed(&self) -> HealthStatus {
+        let probe = Self {
+            http_client: Arc::new(self.http_client.with_retry_policy(RetryPolicy::none())),
+            config: self.config.clone(),
+        };
+        let timestamp = Utc::now();
+        let started = Instant::now();
+        let response = probe.http_client.get("").await;
+        let latency = started.elapsed();
+
+        let error = match response {
+            Ok(response) if response.status().is_success() => None,
+            Ok(response) => Some(probe.http_client.error_for_response(response, None).await),
+            Err(error) => Some(error),
+        };
+        if let Some(error) = error {
+            let mut status = HealthStatus::new(false, timestamp, latency);
+            status.record_error(error);
+            return status;
+        }
+
+        let (version, running) = tokio::join!(probe.server_version(), probe.list_running_models());
+        let mut status = HealthStatus::new(true, timestamp, latency);
+        match version {
+            Ok(version) => status.version = Some(version),
+            Err(e) => status.record_error(e),
+        }
+        match running {
+            Ok(running) => status.loaded_models = Some(running.models.len()),
+            Err(e) => status.record_error(e),
+        }
+        status
+    }
+
+    /// Get the Ollama server version, parsed for comparison
+    pub async fn server_version(&self) -> Result<VersionInfo> {
+        let response = self.http_client.get("api/version").await?;
+        if !response.status().is_success() {
+            return Err(self.http_client.error_for_response(response, None).await);
+        }
+        self.http_client.json(response).await
+    }
+
+    /// Detect which optional features the server supports
+    ///
+    /// The server version is read once per client and cached; requests that
+    /// use an unsupported feature fail early with
+    /// [`OllamaError::InvalidParameter`], and embeddings fall back to the
+    /// legacy endpoint on servers without `/api/embed`.
+    pub async fn capabilities(&self) -> Result<ServerCapabilities> {
+        CapabilitiesApi::detect(&self.http_client).await
+    }
+
     /// Get the Ollama server version
+    ///
+    /// Returns the raw response; see [`server_version`](Self::server_version) for a parsed version.
     pub async fn version(&self) -> Result<serde_json::Value> {
         let response = self.http_client.get("api/version").await?;
-        let json: serde_json::Value =
-            response.json().await.map_err(|e| OllamaError::InvalidResponse(e.to_string()))?;
+        if !response.status().is_success() {
+            return Err(self.http_client.error_for_response(response, None).await);
+        }
+        let json: serde_json::Value = self.http_client.json(response).await?;
         Ok(json)
     }
 
@@ -137,21 +321,58 @@ impl OllamaClient {
     pub async fn pull_model_stream(
         &self,
         name: &str,
-    ) -> Result<impl tokio_stream::Stream<Item = Result<serde_json::Value>>> {
+    ) -> Result<impl tokio_stream::Stream<Item = Result<PullProgress>> + use<>> {
         ModelsApi::pull_model_stream(&self.http_client, name).await
     }
 
+    /// Pull a model, reconnecting with backoff when the progress stream fails
+    ///
+    /// Gives up after `policy.max_retries` reconnects in a row make no progress,
+    /// or after [`ResilientPullOptions::max_reconnects`] reconnects in total.
+    /// See [`ModelsApi::pull_model_resilient`].
+    pub fn pull_model_resilient<O: Into<ResilientPullOptions>>(
+        &self,
+        name: &str,
+        options: O,
+    ) -> impl tokio_stream::Stream<Item = Result<PullProgress>> + use<O> {
+        ModelsApi::pull_model_resilient(&self.http_client, name, options)
+    }
+
+    /// Push a model to a registry
+    ///
+    /// Set `insecure` to allow an insecure connection to a private registry.
+    pub async fn push_model(&self, name: &str, insecure: bool) -> Result<()> {
+        ModelsApi::push_model(&self.http_client, name, insecure).await
+    }
+
+    /// Push a model with streaming progress updates
+    pub async fn push_model_stream(
+        &self,
+        name: &str,
+        insecure: bool,
+    ) -> Result<impl tokio_stream::Stream<Item = Result<PushProgress>> + use<>> {
+        ModelsApi::push_model_stream(&self.http_client, name, insecure).await
+    }
+
     /// Create a new model from a Modelfile
     pub async fn create_model(&self, name: &str, modelfile: &str) -> Result<()> {
         ModelsApi::create_model(&self.http_client, name, modelfile, false).await
     }
 
+    /// Create a builder for a structured model create request
+    ///
+    /// Prefer this over [`create_model`](Self::create_model) on current servers,
+    /// which build models from structured fields rather than a Modelfile.
+    pub fn create(&self) -> CreateModelBuilder {
+        CreateModelBuilder::new(self.http_client.clone())
+    }
+
     /// Create a model with streaming progress updates
     pub async fn create_model_stream(
         &self,
         name: &str,
         modelfile: &str,
-    ) -> Result<impl tokio_stream::Stream<Item = Result<serde_json::Value>>> {
+    ) -> Result<impl tokio_stream::Stream<Item = Result<CreateProgress>> + use<>> {
         ModelsApi::create_model_stream(&self.http_client, name, modelfile).await
     }
 
@@ -178,7 +399,9 @@ impl OllamaClient {
     }
 
     /// Create/upload a blob
-    pub async fn create_blob(&self, digest: &str, data: Vec<u8>) -> Result<()> {
+    ///
+    /// `data` may be bytes or a streaming [`reqwest::Body`].
+    pub async fn create_blob<B: Into<reqwest::Body>>(&self, digest: &str, data: B) -> Result<()> {
         BlobsApi::create_blob(&self.http_client, digest, data).await
     }
 }
@@ -228,12 +451,28 @@ impl EmbedRequestBuilder {
         self
     }
 
+    /// Override the timeout, retries or headers for this request only
+    pub fn request_options(mut self, options: RequestOptions) -> Self {
+        self.http_client = Arc::new(self.http_client.with_request_options(&options));
+        self
+    }
+
+    /// Cancel the request when the token is cancelled
+    pub fn cancellation_token(mut self, token: CancellationToken) -> Self {
+        self.http_client = Arc::new(self.http_client.with_cancellation(token));
+        self
+    }
+
     /// Send the embedding request
     pub async fn send(self) -> Result<crate::models::embedding::EmbedResponse> {
         EmbeddingsApi::embed(&self.http_client, self.request).await
     }
 }
 
+fn non_empty_env(name: &str) -> Option<String> {
+    env::var(name).ok().filter(|value| !value.trim().is_empty())
+}
+
 fn parse_env_headers(raw: &str) -> Result<Vec<(String, String)>> {
     if raw.trim().is_empty() {
         return Ok(Vec::new());
@@ -267,10 +506,13 @@ fn parse_env_headers(raw: &str) -> Result<Vec<(String, String)>> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::models::common::{KeepAlive, Options};
+    use crate::models::{
+        common::{KeepAlive, Options},
+        model_name::ModelName,
+    };
     use wiremock::{
         Mock, MockServer, ResponseTemplate,
-        matchers::{method, path},
+        matchers::{body_json, method, path},
     };
 
     #[test]
@@ -395,7 +637,82 @@ mod tests {
         let result = client.version().await;
 
         assert!(result.is_err());
-        assert!(matches!(result.unwrap_err(), OllamaError::InvalidResponse(_)));
+        assert!(matches!(result.unwrap_err(), OllamaError::InvalidResponse(..)));
+    }
+
+    #[tokio::test]
+    async fn test_health_detailed() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path(""))
+            .respond_with(ResponseTemplate::new(200))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/version"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(serde_json::json!({"version": "0.6.2"})),
+            )
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/ps"))
+            .respond_with(
+                ResponseTemplate::new(500).set_body_json(serde_json::json!({"error": "busy"})),
+            )
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let client = OllamaClient::new(mock_server.uri()).unwrap();
+        let status = client.health_detailed().await;
+
+        assert!(status.healthy);
+        assert!(status.version.as_ref().unwrap().at_least(0, 6, 0));
+        assert_eq!(status.loaded_models, None);
+        assert_eq!(status.error.as_ref().and_then(|error| error.status_code()), Some(500));
+
+        let json = serde_json::to_value(status.clone()).unwrap();
+        assert!(json["error_message"].as_str().unwrap().starts_with("Server error: 500 - busy"));
+        assert!(json.get("error").is_none());
+        let restored: HealthStatus = serde_json::from_value(json).unwrap();
+        assert!(restored.healthy && restored.error.is_none());
+        assert_eq!(restored.error_message, status.error_message);
+
+        mock_server.reset().await;
+        Mock::given(method("GET"))
+            .and(path(""))
+            .respond_with(ResponseTemplate::new(503))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let status = client.health_detailed().await;
+        assert!(!status.healthy);
+        assert!(status.version.is_none());
+        assert_eq!(status.error.and_then(|error| error.status_code()), Some(503));
+    }
+
+    #[tokio::test]
+    async fn test_server_version() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/api/version"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(serde_json::json!({"version": "0.5.0-rc1"})),
+            )
+            .mount(&mock_server)
+            .await;
+
+        let client = OllamaClient::new(mock_server.uri()).unwrap();
+        let version = client.server_version().await.unwrap();
+
+        assert_eq!(version.pre_release.as_deref(), Some("rc1"));
+        assert!(version < VersionInfo::parse("0.5.0").unwrap());
+        assert!(!version.at_least(0, 5, 0));
     }
 
     #[test]
@@ -523,6 +840,47 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[tokio::test]
+    async fn test_model_names_are_validated_before_sending() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/api/show"))
+            .and(body_json(serde_json::json!({"name": "team/mario:latest", "verbose": false})))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_string(r#"{"modelfile": "FROM test"}"#),
+            )
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/api/chat"))
+            .and(body_json(serde_json::json!({
+                "model": "team/mario:latest",
+                "messages": [{"role": "user", "content": "hi"}],
+                "stream": false
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "model": "team/mario:latest",
+                "created_at": "2024-01-01T00:00:00Z",
+                "message": {"role": "assistant", "content": "It's-a me!"},
+                "done": true
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let client = OllamaClient::new(mock_server.uri()).unwrap();
+        let name: ModelName = "team/mario".parse().unwrap();
+        client.show_model(&name).await.unwrap();
+
+        let error = client.copy_model(&name, "bad name").await.unwrap_err();
+        assert!(matches!(error, OllamaError::InvalidModelName(..)));
+        let error = client.chat().model("-mario").add_user_message("hi").send().await.unwrap_err();
+        assert!(matches!(error, OllamaError::InvalidModelName(..)));
+        client.chat().model(&name).add_user_message("hi").send().await.unwrap();
+    }
+
     #[tokio::test]
     async fn test_blob_exists_delegation() {
         let mock_server = MockServer::start().await;
@@ -558,4 +916,169 @@ mod tests {
 
         assert!(result.is_ok());
     }
+
+    #[tokio::test]
+    async fn test_with_cancellation_aborts_model_operation() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/api/pull"))
+            .respond_with(ResponseTemplate::new(200).set_delay(Duration::from_secs(30)))
+            .mount(&mock_server)
+            .await;
+
+        let client = OllamaClient::new(mock_server.uri()).unwrap();
+        let token = CancellationToken::new();
+        let trigger = token.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            trigger.cancel();
+        });
+
+        let started = std::time::Instant::now();
+        let result = client.with_cancellation(token).pull_model("llama3").await;
+
+        assert!(matches!(result, Err(OllamaError::Cancelled(_))));
+        assert!(started.elapsed() < Duration::from_secs(5));
+    }
+
+    #[test]
+    fn test_with_cancellation_does_not_affect_original_client() {
+        let client = OllamaClient::new("http://localhost:11434").unwrap();
+        let scoped = client.with_cancellation(CancellationToken::new());
+
+        assert!(client.http_client.cancellation_token().is_none());
+        assert!(scoped.http_client.cancellation_token().is_some());
+    }
+
+    #[derive(Debug, Default)]
+    struct InMemoryTransport {
+        requests: std::sync::Mutex<Vec<(reqwest::Method, String)>>,
+    }
+
+    impl Transport for InMemoryTransport {
+        fn execute(
+            &self,
+            request: reqwest::Request,
+        ) -> futures_util::future::BoxFuture<'_, Result<reqwest::Response>> {
+            self.requests
+                .lock()
+                .unwrap()
+                .push((request.method().clone(), request.url().to_string()));
+            let response =
+                http::Response::builder().status(200).body(r#"{"version":"0.6.0"}"#).unwrap();
+            Box::pin(async move { Ok(reqwest::Response::from(response)) })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_with_transport_routes_requests() {
+        let transport = Arc::new(InMemoryTransport::default());
+        let config = ClientConfig::new("http://ollama.invalid:11434").unwrap();
+        let client = OllamaClient::with_transport(config, transport.clone());
+
+        let version = client.version().await.unwrap();
+
+        assert_eq!(version["version"], "0.6.0");
+        let requests = transport.requests.lock().unwrap();
+        assert_eq!(
+            *requests,
+            vec![(reqwest::Method::GET, "http://ollama.invalid:11434/api/version".to_string())]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_with_reqwest_client() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/api/version"))
+            .and(wiremock::matchers::header("user-agent", "shared-pool"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(serde_json::json!({"version": "0.6.0"})),
+            )
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let reqwest_client = reqwest::Client::builder().user_agent("shared-pool").build().unwrap();
+        let config = ClientConfig::new(mock_server.uri()).unwrap();
+        let client = OllamaClient::with_reqwest_client(config, reqwest_client);
+
+        let version = client.version().await.unwrap();
+        assert_eq!(version["version"], "0.6.0");
+    }
+
+    #[tokio::test]
+    async fn test_embed_request_options_headers() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/api/embed"))
+            .and(wiremock::matchers::header("x-priority", "low"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "model": "nomic-embed-text",
+                "embeddings": [[0.1, 0.2]]
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let client = OllamaClient::new(mock_server.uri()).unwrap();
+        let headers = [("X-Priority".to_string(), "low".to_string())].into();
+        let response = client
+            .embed()
+            .model("nomic-embed-text")
+            .input("hello")
+            .request_options(RequestOptions { headers: Some(headers), ..Default::default() })
+            .send()
+            .await
+            .unwrap();
+
+        assert_eq!(response.embeddings.len(), 1);
+    }
+
+    #[test]
+    fn test_with_request_options_does_not_affect_original_client() {
+        let client = OllamaClient::new("http://localhost:11434").unwrap();
+        let scoped = client
+            .with_request_options(RequestOptions { timeout: Some(600), ..Default::default() });
+
+        assert!(client.http_client.request_options().timeout.is_none());
+        assert_eq!(scoped.http_client.request_options().timeout, Some(600));
+    }
+
+    #[tokio::test]
+    async fn test_path_prefixed_base_url() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/ollama/api/version"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(serde_json::json!({"version": "0.6.0"})),
+            )
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/ollama/api/tags"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(serde_json::json!({"models": []})),
+            )
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("DELETE"))
+            .and(path("/ollama/api/delete"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let client = OllamaClient::new(format!("{}/ollama", mock_server.uri())).unwrap();
+
+        assert_eq!(client.version().await.unwrap()["version"], "0.6.0");
+        assert!(client.list_models().await.unwrap().models.is_empty());
+        client.delete_model("old").await.unwrap();
+    }
 }
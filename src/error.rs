@@ -1,16 +1,25 @@
 //! Error types for the Ollama SDK
 
+use std::{fmt, sync::Arc, time::Duration};
 use thiserror::Error;
 
 /// Result type alias for Ollama operations
 pub type Result<T> = std::result::Result<T, OllamaError>;
 
 /// Comprehensive error types for Ollama SDK operations
+///
+/// Variants that describe a failed request end with an [`ErrorContext`] field.
+/// Errors returned by the client fill it in with the request's method,
+/// endpoint, model, attempts, elapsed time, request id and, for responses,
+/// the server's status and message; it is `None` for errors built by hand.
+/// The context is appended to the error's `Display` and read with
+/// [`OllamaError::context`] and the accessors next to it. Match the field with
+/// `_` or `..`, for example `OllamaError::ModelNotFound(model, _)`.
 #[derive(Error, Debug)]
 pub enum OllamaError {
     /// Network-related errors (connection issues, timeouts, etc.)
-    #[error("Network error: {0}")]
-    NetworkError(#[from] reqwest::Error),
+    #[error("Network error: {0}{ctx}", ctx = suffix(.1))]
+    NetworkError(#[source] reqwest::Error, Option<Box<ErrorContext>>),
 
     /// JSON serialization/deserialization errors
     #[error("JSON error: {0}")]
@@ -21,36 +30,53 @@ pub enum OllamaError {
     UrlError(#[from] url::ParseError),
 
     /// Model not found on the server
-    #[error("Model '{0}' not found")]
-    ModelNotFound(String),
+    #[error("Model '{0}' not found{ctx}", ctx = suffix(.1))]
+    ModelNotFound(String, Option<Box<ErrorContext>>),
 
     /// Invalid model name format
-    #[error("Invalid model name: {0}")]
-    InvalidModelName(String),
+    #[error("Invalid model name: {0}{ctx}", ctx = suffix(.1))]
+    InvalidModelName(String, Option<Box<ErrorContext>>),
 
     /// Server returned an error response
-    #[error("Server error: {status} - {message}")]
-    ServerError { status: u16, message: String },
+    #[error("Server error: {status} - {message}{ctx}", ctx = suffix(context))]
+    ServerError { status: u16, message: String, context: Option<Box<ErrorContext>> },
 
     /// Request timeout
-    #[error("Request timeout")]
-    Timeout,
+    #[error("Request timeout{ctx}", ctx = suffix(.0))]
+    Timeout(Option<Box<ErrorContext>>),
+
+    /// Establishing the connection took longer than the connect timeout
+    #[error("Connection timed out{ctx}", ctx = suffix(.0))]
+    ConnectTimeout(Option<Box<ErrorContext>>),
+
+    /// A stream produced no data before the first-chunk timeout elapsed
+    #[error("No stream data received within {0:?}{ctx}", ctx = suffix(.1))]
+    FirstChunkTimeout(std::time::Duration, Option<Box<ErrorContext>>),
+
+    /// A stream produced no data for longer than the idle timeout
+    #[error("Stream idle for more than {0:?}{ctx}", ctx = suffix(.1))]
+    StreamIdleTimeout(std::time::Duration, Option<Box<ErrorContext>>),
 
     /// Invalid API response format
-    #[error("Invalid response format: {0}")]
-    InvalidResponse(String),
+    #[error("Invalid response format: {0}{ctx}", ctx = suffix(.1))]
+    InvalidResponse(String, Option<Box<ErrorContext>>),
 
     /// Authentication error
-    #[error("Authentication failed: {0}")]
-    AuthenticationError(String),
-
-    /// Rate limit exceeded
-    #[error("Rate limit exceeded")]
-    RateLimitExceeded,
+    #[error("Authentication failed: {0}{ctx}", ctx = suffix(.1))]
+    AuthenticationError(String, Option<Box<ErrorContext>>),
+
+    /// The server answered `429 Too Many Requests`
+    #[error("Rate limit exceeded{}{}", format_retry_after(*retry_after), suffix(context))]
+    RateLimitExceeded {
+        /// How long the server asked the client to wait, from `Retry-After`
+        retry_after: Option<std::time::Duration>,
+        /// The request that was rejected
+        context: Option<Box<ErrorContext>>,
+    },
 
     /// Streaming error
-    #[error("Streaming error: {0}")]
-    StreamError(String),
+    #[error("Streaming error: {0}{ctx}", ctx = suffix(.1))]
+    StreamError(String, Option<Box<ErrorContext>>),
 
     /// Configuration error
     #[error("Configuration error: {0}")]
@@ -65,42 +91,269 @@ pub enum OllamaError {
     InvalidParameter { parameter: String, reason: String },
 
     /// Model is currently loading
-    #[error("Model '{0}' is currently loading, please try again")]
-    ModelLoading(String),
+    #[error("Model '{0}' is currently loading, please try again{ctx}", ctx = suffix(.1))]
+    ModelLoading(String, Option<Box<ErrorContext>>),
 
     /// Insufficient system resources
-    #[error("Insufficient resources: {0}")]
-    InsufficientResources(String),
+    #[error("Insufficient resources: {0}{ctx}", ctx = suffix(.1))]
+    InsufficientResources(String, Option<Box<ErrorContext>>),
+
+    /// The endpoint's circuit breaker is open after repeated failures
+    #[error("Circuit open for {endpoint}, retry in {retry_in:?}{ctx}", ctx = suffix(context))]
+    CircuitOpen {
+        /// Endpoint path whose circuit is open
+        endpoint: String,
+        /// Time until the circuit allows a health probe
+        retry_in: std::time::Duration,
+        /// The request that was refused
+        context: Option<Box<ErrorContext>>,
+    },
+
+    /// The request was cancelled through its cancellation token
+    #[error("Request cancelled{ctx}", ctx = suffix(.0))]
+    Cancelled(Option<Box<ErrorContext>>),
 
     /// Generic error for other cases
     #[error("Ollama error: {0}")]
     Other(String),
 }
 
+impl From<reqwest::Error> for OllamaError {
+    fn from(error: reqwest::Error) -> Self {
+        Self::NetworkError(error, None)
+    }
+}
+
+/// The request behind an error returned by the client
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ErrorContext {
+    /// HTTP method, such as `POST`
+    pub method: String,
+    /// Request path, such as `/api/generate`
+    pub endpoint: String,
+    /// Model the request targets, if any
+    pub model: Option<String>,
+    /// Number of attempts made, counting retries
+    pub attempt: u32,
+    /// Time from the start of the call until the error
+    pub elapsed: Duration,
+    /// Request id from the response, or from the request if the response had none
+    pub request_id: Option<String>,
+    /// HTTP status of the response, if one was received
+    pub status: Option<u16>,
+    /// The server's error message, from the `error` field of its response body
+    pub message: Option<String>,
+}
+
+impl fmt::Display for ErrorContext {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.method, self.endpoint)?;
+        if let Some(model) = &self.model {
+            write!(f, ", model {model}")?;
+        }
+        write!(f, ", attempt {}, after {:?}", self.attempt, self.elapsed)?;
+        if let Some(request_id) = &self.request_id {
+            write!(f, ", request id {request_id}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Callback that observes every error a request produces, with its context attached
+pub type ErrorHook = Arc<dyn Fn(&OllamaError) + Send + Sync>;
+
 impl OllamaError {
     /// Check if the error is retryable
     pub fn is_retryable(&self) -> bool {
         matches!(
             self,
-            Self::NetworkError(_)
-                | Self::Timeout
-                | Self::ModelLoading(_)
+            Self::NetworkError(..)
+                | Self::Timeout(_)
+                | Self::ConnectTimeout(_)
+                | Self::ModelLoading(..)
+                | Self::CircuitOpen { .. }
                 | Self::ServerError { status: 500..=599, .. }
         )
     }
 
     /// Check if the error indicates the model is not available
     pub fn is_model_unavailable(&self) -> bool {
-        matches!(self, Self::ModelNotFound(_) | Self::ModelLoading(_))
+        matches!(self, Self::ModelNotFound(..) | Self::ModelLoading(..))
     }
 
-    /// Get the HTTP status code if this is a server error
+    /// Get the HTTP status code of the response that caused the error, if any
+    ///
+    /// `ServerError` always has one; other variants have one when the client
+    /// classified them from a response.
     pub fn status_code(&self) -> Option<u16> {
         match self {
             Self::ServerError { status, .. } => Some(*status),
+            _ => self.context()?.status,
+        }
+    }
+
+    /// How long the server asked the client to wait before trying again
+    pub fn retry_after(&self) -> Option<Duration> {
+        match self {
+            Self::RateLimitExceeded { retry_after, .. } => *retry_after,
             _ => None,
         }
     }
+
+    /// The request that produced the error, if the client attached it
+    pub fn context(&self) -> Option<&ErrorContext> {
+        match self {
+            Self::NetworkError(_, context)
+            | Self::ModelNotFound(_, context)
+            | Self::InvalidModelName(_, context)
+            | Self::ServerError { context, .. }
+            | Self::Timeout(context)
+            | Self::ConnectTimeout(context)
+            | Self::FirstChunkTimeout(_, context)
+            | Self::StreamIdleTimeout(_, context)
+            | Self::InvalidResponse(_, context)
+            | Self::AuthenticationError(_, context)
+            | Self::RateLimitExceeded { context, .. }
+            | Self::StreamError(_, context)
+            | Self::ModelLoading(_, context)
+            | Self::InsufficientResources(_, context)
+            | Self::CircuitOpen { context, .. }
+            | Self::Cancelled(context) => context.as_deref(),
+            Self::JsonError(_)
+            | Self::UrlError(_)
+            | Self::ConfigError(_)
+            | Self::IoError(_)
+            | Self::InvalidParameter { .. }
+            | Self::Other(_) => None,
+        }
+    }
+
+    /// HTTP method of the failed request, such as `POST`
+    pub fn method(&self) -> Option<&str> {
+        Some(&self.context()?.method)
+    }
+
+    /// Path of the failed request, such as `/api/chat`
+    pub fn endpoint(&self) -> Option<&str> {
+        Some(&self.context()?.endpoint)
+    }
+
+    /// Model named by the failed request
+    pub fn model(&self) -> Option<&str> {
+        self.context()?.model.as_deref()
+    }
+
+    /// Number of attempts made before the error, counting retries
+    pub fn attempt(&self) -> Option<u32> {
+        Some(self.context()?.attempt)
+    }
+
+    /// Time from the start of the call until the error
+    pub fn elapsed(&self) -> Option<Duration> {
+        Some(self.context()?.elapsed)
+    }
+
+    /// Request id reported by the server or sent by the client
+    pub fn request_id(&self) -> Option<&str> {
+        self.context()?.request_id.as_deref()
+    }
+
+    /// The server's error message, kept even when the variant only names the model
+    pub fn server_message(&self) -> Option<&str> {
+        self.context()?.message.as_deref()
+    }
+
+    /// Attach `context` unless the error has one already or cannot carry one
+    pub(crate) fn attach(mut self, context: ErrorContext) -> Self {
+        let slot = match &mut self {
+            Self::NetworkError(_, slot)
+            | Self::ModelNotFound(_, slot)
+            | Self::InvalidModelName(_, slot)
+            | Self::ServerError { context: slot, .. }
+            | Self::Timeout(slot)
+            | Self::ConnectTimeout(slot)
+            | Self::FirstChunkTimeout(_, slot)
+            | Self::StreamIdleTimeout(_, slot)
+            | Self::InvalidResponse(_, slot)
+            | Self::AuthenticationError(_, slot)
+            | Self::RateLimitExceeded { context: slot, .. }
+            | Self::StreamError(_, slot)
+            | Self::ModelLoading(_, slot)
+            | Self::InsufficientResources(_, slot)
+            | Self::CircuitOpen { context: slot, .. }
+            | Self::Cancelled(slot) => slot,
+            _ => return self,
+        };
+        if slot.is_none() {
+            *slot = Some(Box::new(context));
+        }
+        self
+    }
+
+    /// Classify a non-success response from its status code and body
+    ///
+    /// Ollama reports failures as `{"error": "..."}`; any other body is used as
+    /// the message verbatim. `model` is the model named by the request, if any,
+    /// and lets a `404` become [`OllamaError::ModelNotFound`]. Errors the client
+    /// returns keep the status and message in their [`ErrorContext`], read with
+    /// [`status_code`](Self::status_code) and
+    /// [`server_message`](Self::server_message).
+    pub fn from_response(status: u16, body: &str, model: Option<&str>) -> Self {
+        let message = server_message(status, body);
+        let lower = message.to_ascii_lowercase();
+        let model = model.filter(|model| !model.is_empty());
+
+        if matches!(status, 401 | 403) {
+            return Self::AuthenticationError(format!("{status} - {message}"), None);
+        }
+        if OUT_OF_MEMORY.iter().any(|pattern| lower.contains(pattern)) {
+            return Self::InsufficientResources(message, None);
+        }
+        match model {
+            Some(model) if status == 404 => Self::ModelNotFound(model.to_string(), None),
+            Some(model) if status == 503 && lower.contains("loading") => {
+                Self::ModelLoading(model.to_string(), None)
+            }
+            _ if lower.contains("invalid model name") => match model {
+                Some(model) => Self::InvalidModelName(format!("{model} ({message})"), None),
+                None => Self::InvalidModelName(message, None),
+            },
+            _ => Self::ServerError { status, message, context: None },
+        }
+    }
+}
+
+/// Fragments of Ollama and llama.cpp messages that mean a model did not fit in memory
+const OUT_OF_MEMORY: &[&str] =
+    &["out of memory", "requires more system memory", "insufficient memory", "not enough memory"];
+
+/// The `error` field of an Ollama error body, or the whole body if it has none
+pub(crate) fn server_message(status: u16, body: &str) -> String {
+    #[derive(serde::Deserialize)]
+    struct ErrorBody {
+        error: String,
+    }
+
+    if let Ok(ErrorBody { error }) = serde_json::from_str(body) {
+        return error;
+    }
+    match body.trim() {
+        "" => http::StatusCode::from_u16(status)
+            .ok()
+            .and_then(|status| status.canonical_reason())
+            .unwrap_or_default()
+            .to_string(),
+        body => body.to_string(),
+    }
+}
+
+fn format_retry_after(retry_after: Option<Duration>) -> String {
+    retry_after.map(|delay| format!(", retry after {delay:?}")).unwrap_or_default()
+}
+
+/// The request context appended to an error message, if any
+fn suffix(context: &Option<Box<ErrorContext>>) -> String {
+    context.as_ref().map(|context| format!(" ({context})")).unwrap_or_default()
 }
 
 #[cfg(test)]
@@ -119,12 +372,12 @@ mod tests {
 
         if let Err(reqwest_error) = result {
             let ollama_error: OllamaError = reqwest_error.into();
-            assert!(matches!(ollama_error, OllamaError::NetworkError(_)));
+            assert!(matches!(ollama_error, OllamaError::NetworkError(..)));
             assert!(ollama_error.to_string().contains("Network error"));
             assert!(ollama_error.is_retryable());
         } else {
             // If the request somehow succeeds, just test the error type directly
-            let test_error = OllamaError::Timeout;
+            let test_error = OllamaError::Timeout(None);
             assert!(test_error.is_retryable());
         }
     }
@@ -163,7 +416,7 @@ mod tests {
 
     #[test]
     fn test_model_not_found() {
-        let error = OllamaError::ModelNotFound("llama3".to_string());
+        let error = OllamaError::ModelNotFound("llama3".to_string(), None);
 
         assert_eq!(error.to_string(), "Model 'llama3' not found");
         assert!(!error.is_retryable());
@@ -173,7 +426,7 @@ mod tests {
 
     #[test]
     fn test_invalid_model_name() {
-        let error = OllamaError::InvalidModelName("invalid/model".to_string());
+        let error = OllamaError::InvalidModelName("invalid/model".to_string(), None);
 
         assert_eq!(error.to_string(), "Invalid model name: invalid/model");
         assert!(!error.is_retryable());
@@ -182,8 +435,11 @@ mod tests {
 
     #[test]
     fn test_server_error_retryable() {
-        let error =
-            OllamaError::ServerError { status: 503, message: "Service Unavailable".to_string() };
+        let error = OllamaError::ServerError {
+            status: 503,
+            message: "Service Unavailable".to_string(),
+            context: None,
+        };
 
         assert!(error.to_string().contains("Server error: 503"));
         assert!(error.is_retryable());
@@ -193,7 +449,11 @@ mod tests {
 
     #[test]
     fn test_server_error_not_retryable() {
-        let error = OllamaError::ServerError { status: 400, message: "Bad Request".to_string() };
+        let error = OllamaError::ServerError {
+            status: 400,
+            message: "Bad Request".to_string(),
+            context: None,
+        };
 
         assert!(error.to_string().contains("Server error: 400"));
         assert!(!error.is_retryable());
@@ -202,7 +462,7 @@ mod tests {
 
     #[test]
     fn test_timeout() {
-        let error = OllamaError::Timeout;
+        let error = OllamaError::Timeout(None);
 
         assert_eq!(error.to_string(), "Request timeout");
         assert!(error.is_retryable());
@@ -210,9 +470,23 @@ mod tests {
         assert_eq!(error.status_code(), None);
     }
 
+    #[test]
+    fn test_stream_timeouts() {
+        let connect = OllamaError::ConnectTimeout(None);
+        let first_chunk = OllamaError::FirstChunkTimeout(std::time::Duration::from_secs(30), None);
+        let idle = OllamaError::StreamIdleTimeout(std::time::Duration::from_secs(5), None);
+
+        assert_eq!(connect.to_string(), "Connection timed out");
+        assert_eq!(first_chunk.to_string(), "No stream data received within 30s");
+        assert_eq!(idle.to_string(), "Stream idle for more than 5s");
+        assert!(connect.is_retryable());
+        assert!(!first_chunk.is_retryable());
+        assert!(!idle.is_retryable());
+    }
+
     #[test]
     fn test_invalid_response() {
-        let error = OllamaError::InvalidResponse("Missing required field".to_string());
+        let error = OllamaError::InvalidResponse("Missing required field".to_string(), None);
 
         assert!(error.to_string().contains("Invalid response format"));
         assert!(!error.is_retryable());
@@ -220,7 +494,7 @@ mod tests {
 
     #[test]
     fn test_authentication_error() {
-        let error = OllamaError::AuthenticationError("Invalid token".to_string());
+        let error = OllamaError::AuthenticationError("Invalid token".to_string(), None);
 
         assert!(error.to_string().contains("Authentication failed"));
         assert!(!error.is_retryable());
@@ -228,15 +502,36 @@ mod tests {
 
     #[test]
     fn test_rate_limit_exceeded() {
-        let error = OllamaError::RateLimitExceeded;
+        let error = OllamaError::RateLimitExceeded { retry_after: None, context: None };
 
         assert_eq!(error.to_string(), "Rate limit exceeded");
         assert!(!error.is_retryable());
+        assert_eq!(error.retry_after(), None);
+
+        let error = OllamaError::RateLimitExceeded {
+            retry_after: Some(std::time::Duration::from_secs(2)),
+            context: None,
+        };
+        assert_eq!(error.to_string(), "Rate limit exceeded, retry after 2s");
+        assert_eq!(error.retry_after(), Some(std::time::Duration::from_secs(2)));
+    }
+
+    #[test]
+    fn test_circuit_open() {
+        let error = OllamaError::CircuitOpen {
+            endpoint: "/api/generate".to_string(),
+            retry_in: std::time::Duration::from_secs(5),
+            context: None,
+        };
+
+        assert_eq!(error.to_string(), "Circuit open for /api/generate, retry in 5s");
+        assert!(error.is_retryable());
+        assert_eq!(error.status_code(), None);
     }
 
     #[test]
     fn test_stream_error() {
-        let error = OllamaError::StreamError("Connection lost".to_string());
+        let error = OllamaError::StreamError("Connection lost".to_string(), None);
 
         assert!(error.to_string().contains("Streaming error"));
         assert!(!error.is_retryable());
@@ -264,7 +559,7 @@ mod tests {
 
     #[test]
     fn test_model_loading() {
-        let error = OllamaError::ModelLoading("llama3".to_string());
+        let error = OllamaError::ModelLoading("llama3".to_string(), None);
 
         assert!(error.to_string().contains("Model 'llama3' is currently loading"));
         assert!(error.is_retryable());
@@ -273,12 +568,21 @@ mod tests {
 
     #[test]
     fn test_insufficient_resources() {
-        let error = OllamaError::InsufficientResources("Out of memory".to_string());
+        let error = OllamaError::InsufficientResources("Out of memory".to_string(), None);
 
         assert!(error.to_string().contains("Insufficient resources"));
         assert!(!error.is_retryable());
     }
 
+    #[test]
+    fn test_cancelled() {
+        let error = OllamaError::Cancelled(None);
+
+        assert_eq!(error.to_string(), "Request cancelled");
+        assert!(!error.is_retryable());
+        assert_eq!(error.status_code(), None);
+    }
+
     #[test]
     fn test_other_error() {
         let error = OllamaError::Other("Unexpected error".to_string());
@@ -289,10 +593,50 @@ mod tests {
 
     #[test]
     fn test_debug_formatting() {
-        let error = OllamaError::ModelNotFound("test-model".to_string());
+        let error = OllamaError::ModelNotFound("test-model".to_string(), None);
         let debug_str = format!("{error:?}");
 
         assert!(debug_str.contains("ModelNotFound"));
         assert!(debug_str.contains("test-model"));
     }
+
+    #[test]
+    fn test_from_response() {
+        let error = OllamaError::from_response(400, r#"{"error":"invalid options"}"#, Some("m"));
+        assert!(
+            matches!(error, OllamaError::ServerError { status: 400, ref message, .. } if message == "invalid options")
+        );
+
+        let error =
+            OllamaError::from_response(404, r#"{"error":"model 'm' not found"}"#, Some("m"));
+        assert!(matches!(error, OllamaError::ModelNotFound(ref model, _) if model == "m"));
+        let error = OllamaError::from_response(404, "404 page not found", None);
+        assert_eq!(error.status_code(), Some(404));
+
+        let error = OllamaError::from_response(403, r#"{"error":"forbidden"}"#, Some("m"));
+        assert_eq!(error.to_string(), "Authentication failed: 403 - forbidden");
+
+        let body = r#"{"error":"model requires more system memory (9.1 GiB) than is available (4.0 GiB)"}"#;
+        let error = OllamaError::from_response(500, body, Some("m"));
+        assert!(
+            matches!(error, OllamaError::InsufficientResources(ref message, _) if message.starts_with("model requires"))
+        );
+
+        let error = OllamaError::from_response(503, r#"{"error":"loading model"}"#, Some("m"));
+        assert!(matches!(error, OllamaError::ModelLoading(ref model, _) if model == "m"));
+        let error =
+            OllamaError::from_response(500, r#"{"error":"error loading model"}"#, Some("m"));
+        assert!(matches!(error, OllamaError::ServerError { status: 500, .. }));
+
+        let error =
+            OllamaError::from_response(400, r#"{"error":"invalid model name"}"#, Some("M!"));
+        assert!(
+            matches!(error, OllamaError::InvalidModelName(ref name, _) if name == "M! (invalid model name)")
+        );
+
+        let error = OllamaError::from_response(502, "", None);
+        assert!(
+            matches!(error, OllamaError::ServerError { ref message, .. } if message == "Bad Gateway")
+        );
+    }
 }
@@ -14,6 +14,7 @@
 //! - Builder pattern for easy request configuration
 //! - Model listing, inspection, pull, create, copy, delete, and running-state operations
 //! - Embedding generation with batch processing
+//! - Optional [`blocking`] client for synchronous code
 //!
 //! For the exact implemented surface and known limitations, see the
 //! [API coverage matrix](https://github.com/ThreatFlux/ollama_rust_sdk/blob/main/docs/api-coverage.md).
@@ -41,11 +42,14 @@
 //! ```
 
 pub mod api;
+#[cfg(feature = "blocking")]
+pub mod blocking;
 pub mod builders;
 pub mod client;
 pub mod config;
 pub mod error;
 pub mod models;
+pub mod pool;
 pub mod streaming;
 pub mod types;
 pub mod utils;
@@ -53,7 +57,8 @@ pub mod utils;
 // Re-export main types for convenience
 pub use client::OllamaClient;
 pub use config::{ClientConfig, ClientConfigBuilder};
-pub use error::{OllamaError, Result};
+pub use error::{ErrorContext, ErrorHook, OllamaError, Result};
+pub use pool::{HostStatus, OllamaPool};
 
 // Re-export commonly used types
 pub use models::{
@@ -61,11 +66,39 @@ pub use models::{
     common::{Options, ToolCall, ToolFunction},
     embedding::{EmbedRequest, EmbedResponse},
     generation::{GenerateRequest, GenerateResponse},
-    model_info::{ModelDetails, ModelInfo, ModelList},
+    model_info::{CreateModelRequest, ModelDetails, ModelInfo, ModelList},
+    model_name::ModelName,
+    modelfile::Modelfile,
+    options::RequestOptions,
 };
 
 // Re-export builders
-pub use builders::{chat_builder::ChatBuilder, generate_builder::GenerateBuilder};
+pub use builders::{
+    chat_builder::ChatBuilder, create_builder::CreateModelBuilder,
+    generate_builder::GenerateBuilder,
+};
 
 // Re-export streaming types
-pub use streaming::stream::{ChatStream, GenerateStream, StreamChunk};
+pub use streaming::{
+    progress::{LayerProgress, ProgressRecord, ProgressState, ProgressTracker},
+    stream::{ChatStream, GenerateStream, StreamChunk},
+};
+
+// Re-export cancellation support
+pub use utils::cancel::CancellationToken;
+
+// Re-export transport and middleware extension points
+pub use utils::{
+    middleware::{Middleware, Next},
+    transport::Transport,
+};
+
+// Re-export client-side limits and circuit breaker state
+pub use utils::{
+    breaker::{CircuitBreakerConfig, CircuitState, CircuitStatus},
+    limit::RateLimits,
+    retry::{ResilientPullOptions, RetryPolicy},
+};
+
+// Re-export credential providers
+pub use utils::auth::{BearerToken, CredentialProvider, EnvApiKey, RefreshingToken, TokenFile};
@@ -3,7 +3,7 @@
 //! A command-line interface for interacting with the Ollama API using the Rust SDK.
 
 use clap::{Parser, Subcommand};
-use ollama_rust_sdk::{OllamaClient, OllamaError};
+use ollama_rust_sdk::{OllamaClient, OllamaError, ProgressTracker};
 use std::io::{self, Write};
 use tokio_stream::StreamExt;
 
@@ -75,6 +75,14 @@ enum ModelCommands {
         /// Model name to pull
         name: String,
     },
+    /// Push a model to a registry
+    Push {
+        /// Model name to push, including the namespace
+        name: String,
+        /// Allow insecure connections to the registry
+        #[arg(long)]
+        insecure: bool,
+    },
     /// Delete a model
     Delete {
         /// Model name to delete
@@ -241,16 +249,33 @@ async fn handle_model_commands(
                     println!("Quantization Level: {}", details.quantization_level);
                 }
             }
-            Err(OllamaError::ModelNotFound(_)) => {
+            Err(OllamaError::ModelNotFound(..)) => {
                 eprintln!("Model '{name}' not found");
             }
             Err(e) => return Err(e.into()),
         },
         ModelCommands::Pull { name } => {
             println!("Pulling model '{name}'...");
-            client.pull_model(&name).await?;
+            let mut stream = client.pull_model_stream(&name).await?;
+            let mut tracker = ProgressTracker::new();
+            while let Some(progress) = stream.next().await {
+                tracker.update(&progress?);
+                print_progress(&tracker)?;
+            }
+            println!();
             println!("Successfully pulled model '{name}'");
         }
+        ModelCommands::Push { name, insecure } => {
+            println!("Pushing model '{name}'...");
+            let mut stream = client.push_model_stream(&name, insecure).await?;
+            let mut tracker = ProgressTracker::new();
+            while let Some(progress) = stream.next().await {
+                tracker.update(&progress?);
+                print_progress(&tracker)?;
+            }
+            println!();
+            println!("Successfully pushed model '{name}'");
+        }
         ModelCommands::Delete { name } => {
             println!("Deleting model '{name}'...");
             client.delete_model(&name).await?;
@@ -271,3 +296,16 @@ async fn handle_model_commands(
 
     Ok(())
 }
+
+/// Redraw the current line with a tracker's status, percentage, and ETA
+fn print_progress(tracker: &ProgressTracker) -> io::Result<()> {
+    let mut line = tracker.status().to_string();
+    if let Some(fraction) = tracker.fraction() {
+        line.push_str(&format!(" {:.1}%", fraction * 100.0));
+    }
+    if let Some(eta) = tracker.eta().filter(|eta| !eta.is_zero()) {
+        line.push_str(&format!(" ({}s left)", eta.as_secs()));
+    }
+    print!("\r\x1b[2K{line}");
+    io::stdout().flush()
+}
@@ -1,6 +1,9 @@
 //! Chat API request and response models
 
-use crate::models::common::{KeepAlive, Options, ResponseFormat, Tool, ToolCall};
+use crate::{
+    models::common::{KeepAlive, Options, ResponseFormat, Tool, ToolCall},
+    types::Feature,
+};
 use serde::{Deserialize, Serialize};
 
 /// Role of a message in a chat conversation
@@ -37,6 +40,10 @@ pub struct ChatMessage {
     /// Content of the message
     pub content: String,
 
+    /// Thinking output of the assistant, when requested with `think`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub thinking: Option<String>,
+
     /// Images associated with the message (for multimodal models)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub images: Option<Vec<String>>,
@@ -56,6 +63,7 @@ impl ChatMessage {
         Self {
             role: MessageRole::System,
             content: content.into(),
+            thinking: None,
             images: None,
             tool_calls: None,
             tool_call_id: None,
@@ -67,6 +75,7 @@ impl ChatMessage {
         Self {
             role: MessageRole::User,
             content: content.into(),
+            thinking: None,
             images: None,
             tool_calls: None,
             tool_call_id: None,
@@ -78,6 +87,7 @@ impl ChatMessage {
         Self {
             role: MessageRole::Assistant,
             content: content.into(),
+            thinking: None,
             images: None,
             tool_calls: None,
             tool_call_id: None,
@@ -89,6 +99,7 @@ impl ChatMessage {
         Self {
             role: MessageRole::Tool,
             content: content.into(),
+            thinking: None,
             images: None,
             tool_calls: None,
             tool_call_id: Some(tool_call_id.into()),
@@ -140,6 +151,10 @@ pub struct ChatRequest {
     /// Tool choice strategy
     #[serde(skip_serializing_if = "Option::is_none")]
     pub tool_choice: Option<ToolChoice>,
+
+    /// Ask a reasoning model to return its thinking separately
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub think: Option<bool>,
 }
 
 /// Tool choice strategy
@@ -218,6 +233,27 @@ impl ChatRequest {
         self.tool_choice = Some(choice);
         self
     }
+
+    /// Enable or disable thinking output
+    pub fn think(mut self, think: bool) -> Self {
+        self.think = Some(think);
+        self
+    }
+
+    /// Server features this request relies on
+    pub(crate) fn required_features(&self) -> Vec<Feature> {
+        let mut features = Vec::new();
+        if self.tools.as_ref().is_some_and(|tools| !tools.is_empty()) {
+            features.push(Feature::Tools);
+        }
+        if matches!(self.format, Some(ResponseFormat::Schema(_))) {
+            features.push(Feature::StructuredOutputs);
+        }
+        if self.think == Some(true) {
+            features.push(Feature::Thinking);
+        }
+        features
+    }
 }
 
 /// Response from chat completion
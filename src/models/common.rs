@@ -287,6 +287,9 @@ pub enum ResponseFormat {
     Text,
     /// JSON format
     Json,
+    /// JSON matching a schema (structured outputs)
+    #[serde(untagged)]
+    Schema(serde_json::Value),
 }
 
 /// Keep alive configuration
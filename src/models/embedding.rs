@@ -199,6 +199,7 @@ pub struct LegacyEmbeddingResponse {
     pub embedding: Vec<f64>,
 
     /// The model used
+    #[serde(default)]
     pub model: String,
 
     /// Total duration
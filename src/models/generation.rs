@@ -1,6 +1,9 @@
 //! Generation API request and response models
 
-use crate::models::common::{KeepAlive, Options, ResponseFormat};
+use crate::{
+    models::common::{KeepAlive, Options, ResponseFormat},
+    types::Feature,
+};
 use serde::{Deserialize, Serialize};
 
 /// Request for text generation
@@ -47,6 +50,10 @@ pub struct GenerateRequest {
     /// Images to include with the prompt (for multimodal models)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub images: Option<Vec<String>>,
+
+    /// Ask a reasoning model to return its thinking separately
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub think: Option<bool>,
 }
 
 impl GenerateRequest {
@@ -84,6 +91,24 @@ impl GenerateRequest {
         self.keep_alive = Some(keep_alive);
         self
     }
+
+    /// Enable or disable thinking output
+    pub fn think(mut self, think: bool) -> Self {
+        self.think = Some(think);
+        self
+    }
+
+    /// Server features this request relies on
+    pub(crate) fn required_features(&self) -> Vec<Feature> {
+        let mut features = Vec::new();
+        if matches!(self.format, Some(ResponseFormat::Schema(_))) {
+            features.push(Feature::StructuredOutputs);
+        }
+        if self.think == Some(true) {
+            features.push(Feature::Thinking);
+        }
+        features
+    }
 }
 
 /// Response from text generation
@@ -95,6 +120,10 @@ pub struct GenerateResponse {
     /// The generated response text
     pub response: String,
 
+    /// Thinking output, when requested with `think`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub thinking: Option<String>,
+
     /// Whether this is the final response
     pub done: bool,
 
@@ -188,6 +217,7 @@ mod tests {
         let response = GenerateResponse {
             model: "test".to_string(),
             response: "test".to_string(),
+            thinking: None,
             done: true,
             context: None,
             total_duration: Some(2_000_000_000), // 2 seconds
@@ -5,4 +5,6 @@ pub mod common;
 pub mod embedding;
 pub mod generation;
 pub mod model_info;
+pub mod model_name;
+pub mod modelfile;
 pub mod options;
@@ -1,7 +1,17 @@
 //! Model information and management structures
 
+use crate::{
+    error::Result,
+    models::{
+        chat::ChatMessage,
+        common::Options,
+        model_name::{ModelName, split_tag},
+        modelfile::Modelfile,
+    },
+};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
 /// Information about a single model
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -63,6 +73,30 @@ pub struct ModelInfo {
     pub messages: Option<Vec<crate::models::chat::ChatMessage>>,
 }
 
+impl ModelInfo {
+    /// Parse the `modelfile` field, if the server returned one
+    pub fn parsed_modelfile(&self) -> Option<Result<Modelfile>> {
+        self.modelfile.as_deref().map(Modelfile::parse)
+    }
+
+    /// Parse the `parameters` field into generation options
+    ///
+    /// # Errors
+    /// Returns [`OllamaError::InvalidParameter`](crate::OllamaError::InvalidParameter)
+    /// if a line cannot be parsed or its value does not fit its field.
+    pub fn options(&self) -> Result<Options> {
+        let parameters: String = self
+            .parameters
+            .as_deref()
+            .unwrap_or_default()
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| format!("PARAMETER {line}\n"))
+            .collect();
+        Modelfile::parse(&parameters)?.options()
+    }
+}
+
 /// Detailed technical information about a model
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ModelDetails {
@@ -166,6 +200,37 @@ pub struct CreateProgress {
     /// Progress details
     #[serde(skip_serializing_if = "Option::is_none")]
     pub detail: Option<String>,
+
+    /// Digest of the layer being processed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub digest: Option<String>,
+
+    /// Total bytes of the layer
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub total: Option<u64>,
+
+    /// Bytes of the layer completed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub completed: Option<u64>,
+}
+
+/// Model push progress information
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PushProgress {
+    /// Status of the push operation
+    pub status: String,
+
+    /// Digest of the layer being uploaded
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub digest: Option<String>,
+
+    /// Total bytes of the layer
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub total: Option<u64>,
+
+    /// Bytes of the layer uploaded
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub completed: Option<u64>,
 }
 
 /// Model copy request
@@ -211,6 +276,21 @@ pub struct PullRequest {
     pub insecure: Option<bool>,
 }
 
+/// Model push request
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PushRequest {
+    /// Model name to push, including the registry namespace
+    pub name: String,
+
+    /// Whether to stream progress updates
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stream: Option<bool>,
+
+    /// Allow insecure connections to the registry
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub insecure: Option<bool>,
+}
+
 /// Model create request
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CreateRequest {
@@ -229,6 +309,55 @@ pub struct CreateRequest {
     pub quantize: Option<String>,
 }
 
+/// Structured model create request
+///
+/// Current Ollama servers build models from these fields rather than a Modelfile.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CreateModelRequest {
+    /// Name for the new model
+    pub model: String,
+
+    /// Existing model to build from
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub from: Option<String>,
+
+    /// Model weight files, by file name, as `sha256:` blob digests
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub files: BTreeMap<String, String>,
+
+    /// LoRA adapter files, by file name, as `sha256:` blob digests
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub adapters: BTreeMap<String, String>,
+
+    /// Prompt template
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub template: Option<String>,
+
+    /// License texts
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub license: Vec<String>,
+
+    /// System prompt
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub system: Option<String>,
+
+    /// Default generation parameters
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parameters: Option<Options>,
+
+    /// Messages that start every conversation with the model
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub messages: Vec<ChatMessage>,
+
+    /// Whether to stream progress updates
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stream: Option<bool>,
+
+    /// Quantization type for unquantized weights, such as `q4_K_M`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub quantize: Option<String>,
+}
+
 impl Model {
     /// Get a human-readable size string
     pub fn size_string(&self) -> String {
@@ -241,13 +370,24 @@ impl Model {
     }
 
     /// Get the base model name (without tags)
+    ///
+    /// A registry port, as in `localhost:5000/team/model`, is kept.
     pub fn base_name(&self) -> &str {
-        self.name.split(':').next().unwrap_or(&self.name)
+        split_tag(&self.name).0
     }
 
-    /// Get the model tag (part after ':')
+    /// Get the model tag (part after the last ':' of the final path segment)
     pub fn tag(&self) -> Option<&str> {
-        self.name.split(':').nth(1)
+        split_tag(&self.name).1
+    }
+
+    /// Parse the name into its host, namespace, model and tag
+    ///
+    /// # Errors
+    /// Returns [`OllamaError::InvalidModelName`](crate::OllamaError::InvalidModelName)
+    /// if the name is invalid.
+    pub fn model_name(&self) -> Result<ModelName> {
+        ModelName::parse(&self.name)
     }
 }
 
@@ -329,6 +469,20 @@ mod tests {
         assert_eq!(model.size_string(), "1.0 GB");
     }
 
+    #[test]
+    fn test_model_info_parameters_and_modelfile() {
+        let info: ModelInfo = serde_json::from_value(serde_json::json!({
+            "modelfile": "FROM llama3.2\nPARAMETER num_ctx 8192\n",
+            "parameters": "num_ctx                        8192\nstop                           \"<|eot_id|>\"\nstop                           \"<|end|>\""
+        }))
+        .unwrap();
+
+        let options = info.options().unwrap();
+        assert_eq!(options.num_ctx, Some(8192));
+        assert_eq!(options.stop, Some(vec!["<|eot_id|>".to_string(), "<|end|>".to_string()]));
+        assert_eq!(info.parsed_modelfile().unwrap().unwrap().from(), Some("llama3.2"));
+    }
+
     #[test]
     fn test_pull_progress() {
         let progress = PullProgress {
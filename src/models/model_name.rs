@@ -0,0 +1,352 @@
+//! Model name parsing and validation
+
+use crate::error::{OllamaError, Result};
+use serde::{Deserialize, Serialize};
+use std::{
+    fmt,
+    hash::{Hash, Hasher},
+    ops::Deref,
+    str::FromStr,
+};
+
+/// A parsed `[host[:port]/][namespace/]model[:tag]` model name
+///
+/// Omitted parts take Ollama's defaults, so `llama3`, `llama3:latest` and
+/// `registry.ollama.ai/library/llama3:latest` are equal. Comparison ignores
+/// case, as the server does. [`Display`](fmt::Display) writes the shortest
+/// form that parses back to the same name, always including the tag.
+#[derive(Debug, Clone, Eq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct ModelName {
+    host: String,
+    namespace: String,
+    model: String,
+    tag: String,
+    display: String,
+}
+
+/// Part of a model name, which decides its allowed characters and length
+#[derive(Debug, Clone, Copy)]
+enum Part {
+    Host,
+    Namespace,
+    Model,
+    Tag,
+}
+
+impl Part {
+    fn name(self) -> &'static str {
+        match self {
+            Self::Host => "host",
+            Self::Namespace => "namespace",
+            Self::Model => "model",
+            Self::Tag => "tag",
+        }
+    }
+
+    fn max_len(self) -> usize {
+        match self {
+            Self::Host => 350,
+            _ => 80,
+        }
+    }
+
+    fn allows(self, c: char) -> bool {
+        match c {
+            c if c.is_ascii_alphanumeric() => true,
+            '_' | '-' => true,
+            '.' => !matches!(self, Self::Namespace),
+            ':' => matches!(self, Self::Host),
+            _ => false,
+        }
+    }
+}
+
+impl ModelName {
+    /// Registry used when a name has no host
+    pub const DEFAULT_HOST: &'static str = "registry.ollama.ai";
+    /// Namespace used when a name has no namespace
+    pub const DEFAULT_NAMESPACE: &'static str = "library";
+    /// Tag used when a name has no tag
+    pub const DEFAULT_TAG: &'static str = "latest";
+
+    /// Parse and validate a model name
+    ///
+    /// Each part must start with a letter, digit or `_` and otherwise contain
+    /// only letters, digits, `_`, `-` and `.`; namespaces may not contain `.`,
+    /// and only the host may contain `:` for a port. Hosts may be up to 350
+    /// characters and other parts up to 80. An `http://` or `https://` scheme
+    /// on the host is ignored.
+    ///
+    /// # Errors
+    /// Returns [`OllamaError::InvalidModelName`] describing the first invalid part.
+    pub fn parse(name: &str) -> Result<Self> {
+        let invalid =
+            |reason: String| OllamaError::InvalidModelName(format!("{name} ({reason})"), None);
+
+        let rest = name.strip_prefix("https://").or_else(|| name.strip_prefix("http://"));
+        let rest = rest.unwrap_or(name);
+        let (path, tag) = split_tag(rest);
+        let mut parts = path.rsplit('/');
+        let model = parts.next().unwrap_or_default();
+        let namespace = parts.next();
+        let host = parts.next();
+        if parts.next().is_some() {
+            return Err(invalid("too many '/' separated parts".to_string()));
+        }
+
+        for (part, value) in [
+            (Part::Host, host),
+            (Part::Namespace, namespace),
+            (Part::Model, Some(model)),
+            (Part::Tag, tag),
+        ] {
+            if let Some(value) = value {
+                validate(part, value).map_err(invalid)?;
+            }
+        }
+
+        Ok(Self::from_parts(
+            host.unwrap_or(Self::DEFAULT_HOST),
+            namespace.unwrap_or(Self::DEFAULT_NAMESPACE),
+            model,
+            tag.unwrap_or(Self::DEFAULT_TAG),
+        ))
+    }
+
+    fn from_parts(host: &str, namespace: &str, model: &str, tag: &str) -> Self {
+        let display = if !host.eq_ignore_ascii_case(Self::DEFAULT_HOST) {
+            format!("{host}/{namespace}/{model}:{tag}")
+        } else if !namespace.eq_ignore_ascii_case(Self::DEFAULT_NAMESPACE) {
+            format!("{namespace}/{model}:{tag}")
+        } else {
+            format!("{model}:{tag}")
+        };
+        Self {
+            host: host.to_string(),
+            namespace: namespace.to_string(),
+            model: model.to_string(),
+            tag: tag.to_string(),
+            display,
+        }
+    }
+
+    /// Registry host, including any port
+    pub fn host(&self) -> &str {
+        &self.host
+    }
+
+    /// Namespace within the registry
+    pub fn namespace(&self) -> &str {
+        &self.namespace
+    }
+
+    /// Model name without host, namespace or tag
+    pub fn model(&self) -> &str {
+        &self.model
+    }
+
+    /// Tag, such as `latest` or `8b`
+    pub fn tag(&self) -> &str {
+        &self.tag
+    }
+
+    /// Shortest form of the name, as written by [`Display`](fmt::Display)
+    pub fn as_str(&self) -> &str {
+        &self.display
+    }
+
+    /// Fully qualified `host/namespace/model:tag` form
+    pub fn full(&self) -> String {
+        format!("{}/{}/{}:{}", self.host, self.namespace, self.model, self.tag)
+    }
+
+    /// Copy of this name with a different tag
+    ///
+    /// # Errors
+    /// Returns [`OllamaError::InvalidModelName`] if the tag is invalid.
+    pub fn with_tag(&self, tag: &str) -> Result<Self> {
+        validate(Part::Tag, tag)
+            .map_err(|reason| OllamaError::InvalidModelName(format!("{tag} ({reason})"), None))?;
+        Ok(Self::from_parts(&self.host, &self.namespace, &self.model, tag))
+    }
+}
+
+/// Split a name into the part before the tag and the tag, if any
+///
+/// The tag follows the last `:` after the last `/`, so a host port is not
+/// mistaken for a tag.
+pub(crate) fn split_tag(name: &str) -> (&str, Option<&str>) {
+    let start = name.rfind('/').map_or(0, |slash| slash + 1);
+    match name[start..].rfind(':') {
+        Some(colon) => (&name[..start + colon], Some(&name[start + colon + 1..])),
+        None => (name, None),
+    }
+}
+
+fn validate(part: Part, value: &str) -> std::result::Result<(), String> {
+    let name = part.name();
+    if value.is_empty() {
+        return Err(format!("{name} is empty"));
+    }
+    if value.len() > part.max_len() {
+        return Err(format!("{name} is longer than {} characters", part.max_len()));
+    }
+    for (index, c) in value.char_indices() {
+        let allowed =
+            if index == 0 { c.is_ascii_alphanumeric() || c == '_' } else { part.allows(c) };
+        if !allowed {
+            return Err(format!("{name} contains invalid character {c:?}"));
+        }
+    }
+    Ok(())
+}
+
+impl PartialEq for ModelName {
+    fn eq(&self, other: &Self) -> bool {
+        self.host.eq_ignore_ascii_case(&other.host)
+            && self.namespace.eq_ignore_ascii_case(&other.namespace)
+            && self.model.eq_ignore_ascii_case(&other.model)
+            && self.tag.eq_ignore_ascii_case(&other.tag)
+    }
+}
+
+impl Hash for ModelName {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        for part in [&self.host, &self.namespace, &self.model, &self.tag] {
+            part.to_ascii_lowercase().hash(state);
+        }
+    }
+}
+
+impl fmt::Display for ModelName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.display)
+    }
+}
+
+impl FromStr for ModelName {
+    type Err = OllamaError;
+
+    fn from_str(name: &str) -> Result<Self> {
+        Self::parse(name)
+    }
+}
+
+impl TryFrom<&str> for ModelName {
+    type Error = OllamaError;
+
+    fn try_from(name: &str) -> Result<Self> {
+        Self::parse(name)
+    }
+}
+
+impl TryFrom<String> for ModelName {
+    type Error = OllamaError;
+
+    fn try_from(name: String) -> Result<Self> {
+        Self::parse(&name)
+    }
+}
+
+impl Deref for ModelName {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.display
+    }
+}
+
+impl AsRef<str> for ModelName {
+    fn as_ref(&self) -> &str {
+        &self.display
+    }
+}
+
+impl From<ModelName> for String {
+    fn from(name: ModelName) -> Self {
+        name.display
+    }
+}
+
+impl From<&ModelName> for String {
+    fn from(name: &ModelName) -> Self {
+        name.display.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_applies_defaults() {
+        let name = ModelName::parse("llama3.2").unwrap();
+        assert_eq!(
+            (name.host(), name.namespace(), name.model(), name.tag()),
+            ("registry.ollama.ai", "library", "llama3.2", "latest")
+        );
+        assert_eq!(name.to_string(), "llama3.2:latest");
+        assert_eq!(name.full(), "registry.ollama.ai/library/llama3.2:latest");
+
+        let name = ModelName::parse("https://localhost:5000/team/Mario:v1.2").unwrap();
+        assert_eq!(
+            (name.host(), name.namespace(), name.model(), name.tag()),
+            ("localhost:5000", "team", "Mario", "v1.2")
+        );
+        assert_eq!(name.to_string(), "localhost:5000/team/Mario:v1.2");
+        assert_eq!(ModelName::parse("team/mario").unwrap().as_str(), "team/mario:latest");
+    }
+
+    #[test]
+    fn test_equality_across_forms() {
+        let forms = [
+            "llama3",
+            "llama3:latest",
+            "library/llama3",
+            "registry.ollama.ai/library/llama3:latest",
+            "LLaMA3:Latest",
+        ];
+        let names: Vec<ModelName> = forms.iter().map(|form| form.parse().unwrap()).collect();
+        assert!(names.iter().all(|name| *name == names[0]));
+
+        let unique: std::collections::HashSet<_> = names.into_iter().collect();
+        assert_eq!(unique.len(), 1);
+        assert_ne!(ModelName::parse("llama3:8b").unwrap(), ModelName::parse("llama3").unwrap());
+    }
+
+    #[test]
+    fn test_invalid_names_are_rejected() {
+        let long = "m".repeat(81);
+        let cases = [
+            ("", "model is empty"),
+            ("llama3:", "tag is empty"),
+            ("-llama3", "model contains invalid character '-'"),
+            ("llama 3", "model contains invalid character ' '"),
+            ("my.team/llama3", "namespace contains invalid character '.'"),
+            ("a/b/c/d", "too many '/' separated parts"),
+            (long.as_str(), "model is longer than 80 characters"),
+        ];
+        for (name, reason) in cases {
+            let error = ModelName::parse(name).unwrap_err();
+            assert!(
+                matches!(&error, OllamaError::InvalidModelName(message, _) if message.ends_with(&format!("({reason})"))),
+                "{name:?}: {error}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_split_tag_ignores_port() {
+        assert_eq!(split_tag("localhost:5000/ns/m"), ("localhost:5000/ns/m", None));
+        assert_eq!(split_tag("localhost:5000/ns/m:q4"), ("localhost:5000/ns/m", Some("q4")));
+        assert_eq!(split_tag("m"), ("m", None));
+    }
+
+    #[test]
+    fn test_serde_round_trip() {
+        let name: ModelName = serde_json::from_str("\"team/mario:v1\"").unwrap();
+        assert_eq!(serde_json::to_string(&name).unwrap(), "\"team/mario:v1\"");
+        assert!(serde_json::from_str::<ModelName>("\"bad name\"").is_err());
+    }
+}
@@ -0,0 +1,563 @@
+//! Modelfile parsing and writing
+
+use crate::{
+    error::{OllamaError, Result},
+    models::{
+        chat::{ChatMessage, MessageRole},
+        common::Options,
+        model_info::CreateModelRequest,
+    },
+};
+use serde_json::{Map, Value};
+use std::{fmt, str::FromStr};
+
+/// A parsed Modelfile
+///
+/// Instructions keep their order, comments included, so a parsed Modelfile can
+/// be edited and written back with [`Display`](fmt::Display). Values are
+/// re-quoted on output, so the text may differ from the input while parsing
+/// to the same instructions.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Modelfile {
+    /// Instructions in file order
+    pub instructions: Vec<Instruction>,
+}
+
+/// One line or block of a Modelfile
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Instruction {
+    /// `FROM`: base model name or local weights path
+    From(String),
+    /// `PARAMETER name value`
+    Parameter {
+        /// Parameter name, such as `temperature`
+        name: String,
+        /// Unparsed parameter value
+        value: String,
+    },
+    /// `TEMPLATE`: prompt template
+    Template(String),
+    /// `SYSTEM`: system prompt
+    System(String),
+    /// `ADAPTER`: LoRA adapter path
+    Adapter(String),
+    /// `LICENSE`: license text
+    License(String),
+    /// `MESSAGE role content`
+    Message {
+        /// Message role
+        role: MessageRole,
+        /// Message content
+        content: String,
+    },
+    /// `#` comment, holding the text after the `#`
+    Comment(String),
+}
+
+impl Modelfile {
+    /// Parse Modelfile text
+    ///
+    /// Instruction names are case-insensitive. Values may be bare text to the end
+    /// of the line, `"quoted"` with `\"` and `\\` escapes, or `"""triple-quoted"""`
+    /// across several lines.
+    ///
+    /// # Errors
+    /// Returns [`OllamaError::InvalidParameter`] naming the line of the first
+    /// unknown instruction, missing argument, or unterminated quote.
+    pub fn parse(text: &str) -> Result<Self> {
+        Parser { text, pos: 0, line: 1 }.parse()
+    }
+
+    /// Base model from the last `FROM` instruction
+    pub fn from(&self) -> Option<&str> {
+        self.instructions.iter().rev().find_map(|instruction| match instruction {
+            Instruction::From(from) => Some(from.as_str()),
+            _ => None,
+        })
+    }
+
+    /// Template from the last `TEMPLATE` instruction
+    pub fn template(&self) -> Option<&str> {
+        self.instructions.iter().rev().find_map(|instruction| match instruction {
+            Instruction::Template(template) => Some(template.as_str()),
+            _ => None,
+        })
+    }
+
+    /// System prompt from the last `SYSTEM` instruction
+    pub fn system(&self) -> Option<&str> {
+        self.instructions.iter().rev().find_map(|instruction| match instruction {
+            Instruction::System(system) => Some(system.as_str()),
+            _ => None,
+        })
+    }
+
+    /// Every `PARAMETER` as a name and unparsed value
+    pub fn parameters(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.instructions.iter().filter_map(|instruction| match instruction {
+            Instruction::Parameter { name, value } => Some((name.as_str(), value.as_str())),
+            _ => None,
+        })
+    }
+
+    /// Every `ADAPTER` path
+    pub fn adapters(&self) -> impl Iterator<Item = &str> {
+        self.instructions.iter().filter_map(|instruction| match instruction {
+            Instruction::Adapter(adapter) => Some(adapter.as_str()),
+            _ => None,
+        })
+    }
+
+    /// Every `LICENSE` text
+    pub fn licenses(&self) -> impl Iterator<Item = &str> {
+        self.instructions.iter().filter_map(|instruction| match instruction {
+            Instruction::License(license) => Some(license.as_str()),
+            _ => None,
+        })
+    }
+
+    /// Every `MESSAGE` as a chat message
+    pub fn messages(&self) -> Vec<ChatMessage> {
+        self.instructions
+            .iter()
+            .filter_map(|instruction| match instruction {
+                Instruction::Message { role, content } => {
+                    Some(ChatMessage { role: role.clone(), ..ChatMessage::user(content.clone()) })
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Convert the `PARAMETER` lines to generation options
+    ///
+    /// Repeated `stop` lines collect into one list. Parameters that [`Options`]
+    /// has no field for are skipped.
+    ///
+    /// # Errors
+    /// Returns [`OllamaError::InvalidParameter`] if a value does not fit its field.
+    pub fn options(&self) -> Result<Options> {
+        let mut fields = Map::new();
+        for (name, value) in self.parameters() {
+            if name == "stop" {
+                let stop = fields.entry(name).or_insert_with(|| Value::Array(Vec::new()));
+                if let Value::Array(stop) = stop {
+                    stop.push(Value::String(value.to_string()));
+                }
+            } else {
+                fields.insert(name.to_string(), parameter_value(value));
+            }
+        }
+
+        serde_json::from_value(Value::Object(fields)).map_err(|error| {
+            OllamaError::InvalidParameter {
+                parameter: "PARAMETER".to_string(),
+                reason: error.to_string(),
+            }
+        })
+    }
+
+    /// Replace every `PARAMETER` line with the fields set in `options`
+    ///
+    /// The new lines take the place of the first existing `PARAMETER`, or follow
+    /// the last `FROM` when there was none.
+    pub fn set_options(&mut self, options: &Options) {
+        let first = self
+            .instructions
+            .iter()
+            .position(|instruction| matches!(instruction, Instruction::Parameter { .. }));
+        self.instructions
+            .retain(|instruction| !matches!(instruction, Instruction::Parameter { .. }));
+
+        let at = first.unwrap_or_else(|| {
+            self.instructions
+                .iter()
+                .rposition(|instruction| matches!(instruction, Instruction::From(_)))
+                .map_or(self.instructions.len(), |from| from + 1)
+        });
+        let parameters = option_parameters(options);
+        self.instructions.splice(at..at, parameters);
+    }
+
+    /// Convert to a structured create request for a model named `model`
+    ///
+    /// # Errors
+    /// Returns [`OllamaError::InvalidParameter`] if `FROM` or `ADAPTER` names a
+    /// local file, which must be uploaded as a blob with
+    /// [`CreateModelBuilder`](crate::builders::create_builder::CreateModelBuilder)
+    /// instead, or if a parameter value does not fit its field.
+    pub fn to_create_request(&self, model: &str) -> Result<CreateModelRequest> {
+        if let Some(from) = self.from().filter(|from| is_local_path(from)) {
+            return Err(OllamaError::InvalidParameter {
+                parameter: "FROM".to_string(),
+                reason: format!("{from} is a local file; upload it with local_file instead"),
+            });
+        }
+        if let Some(adapter) = self.adapters().next() {
+            return Err(OllamaError::InvalidParameter {
+                parameter: "ADAPTER".to_string(),
+                reason: format!("{adapter} is a local file; upload it with local_adapter instead"),
+            });
+        }
+
+        let has_parameters = self.parameters().next().is_some();
+        Ok(CreateModelRequest {
+            model: model.to_string(),
+            from: self.from().map(str::to_string),
+            template: self.template().map(str::to_string),
+            system: self.system().map(str::to_string),
+            license: self.licenses().map(str::to_string).collect(),
+            parameters: if has_parameters { Some(self.options()?) } else { None },
+            messages: self.messages(),
+            ..CreateModelRequest::default()
+        })
+    }
+}
+
+impl FromStr for Modelfile {
+    type Err = OllamaError;
+
+    fn from_str(text: &str) -> Result<Self> {
+        Self::parse(text)
+    }
+}
+
+impl fmt::Display for Modelfile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for instruction in &self.instructions {
+            writeln!(f, "{instruction}")?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::From(from) => write!(f, "FROM {}", quote(from)),
+            Self::Parameter { name, value } => write!(f, "PARAMETER {name} {}", quote(value)),
+            Self::Template(template) => write!(f, "TEMPLATE {}", quote(template)),
+            Self::System(system) => write!(f, "SYSTEM {}", quote(system)),
+            Self::Adapter(adapter) => write!(f, "ADAPTER {}", quote(adapter)),
+            Self::License(license) => write!(f, "LICENSE {}", quote(license)),
+            Self::Message { role, content } => write!(f, "MESSAGE {role} {}", quote(content)),
+            Self::Comment(comment) => write!(f, "#{comment}"),
+        }
+    }
+}
+
+/// Write a value so that it parses back unchanged
+fn quote(value: &str) -> String {
+    let bare = !value.is_empty()
+        && !value.contains('\n')
+        && !value.starts_with('"')
+        && value.trim() == value;
+    if bare {
+        value.to_string()
+    } else if !value.contains("\"\"\"") && !value.ends_with('"') {
+        format!("\"\"\"{value}\"\"\"")
+    } else {
+        format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+    }
+}
+
+/// Type a parameter value for deserializing into [`Options`]
+fn parameter_value(value: &str) -> Value {
+    if let Ok(integer) = value.parse::<i64>() {
+        return integer.into();
+    }
+    if let Some(number) = value.parse::<f64>().ok().and_then(serde_json::Number::from_f64) {
+        return Value::Number(number);
+    }
+    match value {
+        "true" => Value::Bool(true),
+        "false" => Value::Bool(false),
+        _ => Value::String(value.to_string()),
+    }
+}
+
+/// `PARAMETER` instructions for every field set in `options`
+fn option_parameters(options: &Options) -> Vec<Instruction> {
+    let Ok(Value::Object(fields)) = serde_json::to_value(options) else {
+        return Vec::new();
+    };
+
+    let mut parameters = Vec::new();
+    for (name, value) in fields {
+        let values = match value {
+            Value::Array(values) => values,
+            value => vec![value],
+        };
+        for value in values {
+            let value = match value {
+                Value::String(value) => value,
+                value => value.to_string(),
+            };
+            parameters.push(Instruction::Parameter { name: name.clone(), value });
+        }
+    }
+    parameters
+}
+
+/// Whether a `FROM` value names a file rather than a model
+fn is_local_path(from: &str) -> bool {
+    let lower = from.to_ascii_lowercase();
+    from.starts_with(['.', '/', '~', '\\'])
+        || lower.ends_with(".gguf")
+        || lower.ends_with(".safetensors")
+}
+
+/// Line-oriented Modelfile parser
+struct Parser<'a> {
+    text: &'a str,
+    pos: usize,
+    line: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn parse(mut self) -> Result<Modelfile> {
+        let mut instructions = Vec::new();
+        while self.pos < self.text.len() {
+            self.skip_whitespace();
+            let rest = self.rest();
+            if rest.is_empty() {
+                break;
+            }
+            if rest.starts_with('\n') {
+                self.advance(1);
+                continue;
+            }
+            if let Some(comment) = rest.strip_prefix('#') {
+                let comment = comment.split('\n').next().unwrap_or_default();
+                instructions.push(Instruction::Comment(comment.trim_end_matches('\r').to_string()));
+                self.advance(comment.len() + 1);
+                continue;
+            }
+            instructions.push(self.instruction()?);
+        }
+        Ok(Modelfile { instructions })
+    }
+
+    fn instruction(&mut self) -> Result<Instruction> {
+        let command = self.word();
+        let instruction = match command.to_ascii_uppercase().as_str() {
+            "FROM" => Instruction::From(self.value(&command)?),
+            "PARAMETER" => {
+                let name = self.word();
+                if name.is_empty() {
+                    return Err(self.error(format!("{command} is missing a name")));
+                }
+                Instruction::Parameter { name, value: self.value(&command)? }
+            }
+            "TEMPLATE" => Instruction::Template(self.value(&command)?),
+            "SYSTEM" => Instruction::System(self.value(&command)?),
+            "ADAPTER" => Instruction::Adapter(self.value(&command)?),
+            "LICENSE" => Instruction::License(self.value(&command)?),
+            "MESSAGE" => {
+                let word = self.word();
+                let role = serde_json::from_value(Value::String(word.to_ascii_lowercase()))
+                    .map_err(|_| self.error(format!("unknown message role '{word}'")))?;
+                Instruction::Message { role, content: self.value(&command)? }
+            }
+            _ => return Err(self.error(format!("unknown instruction '{command}'"))),
+        };
+
+        self.skip_whitespace();
+        if !self.rest().is_empty() && !self.rest().starts_with('\n') {
+            return Err(self.error(format!("unexpected text after {command} value")));
+        }
+        Ok(instruction)
+    }
+
+    /// Read a bare, quoted, or triple-quoted value
+    fn value(&mut self, command: &str) -> Result<String> {
+        self.skip_whitespace();
+        let rest = self.rest();
+        if let Some(body) = rest.strip_prefix("\"\"\"") {
+            let Some(end) = body.find("\"\"\"") else {
+                return Err(self.error(format!("unterminated \"\"\" in {command}")));
+            };
+            let value = body[..end].to_string();
+            self.advance(end + 6);
+            return Ok(value);
+        }
+        if let Some(body) = rest.strip_prefix('"') {
+            let mut value = String::new();
+            let mut chars = body.char_indices();
+            while let Some((index, c)) = chars.next() {
+                match c {
+                    '"' => {
+                        self.advance(index + 2);
+                        return Ok(value);
+                    }
+                    '\\' => match chars.next() {
+                        Some((_, escaped @ ('"' | '\\'))) => value.push(escaped),
+                        Some((_, other)) => value.extend(['\\', other]),
+                        None => break,
+                    },
+                    c => value.push(c),
+                }
+            }
+            return Err(self.error(format!("unterminated quote in {command}")));
+        }
+
+        let line = rest.split('\n').next().unwrap_or_default();
+        self.advance(line.len());
+        let value = line.trim();
+        if value.is_empty() {
+            return Err(self.error(format!("{command} is missing a value")));
+        }
+        Ok(value.to_string())
+    }
+
+    fn word(&mut self) -> String {
+        self.skip_whitespace();
+        let rest = self.rest();
+        let end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+        self.advance(end);
+        rest[..end].to_string()
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = self.rest();
+        let trimmed = rest.trim_start_matches([' ', '\t', '\r']);
+        self.advance(rest.len() - trimmed.len());
+    }
+
+    fn rest(&self) -> &'a str {
+        &self.text[self.pos..]
+    }
+
+    fn advance(&mut self, len: usize) {
+        let end = (self.pos + len).min(self.text.len());
+        self.line += self.text[self.pos..end].matches('\n').count();
+        self.pos = end;
+    }
+
+    fn error(&self, reason: String) -> OllamaError {
+        OllamaError::InvalidParameter {
+            parameter: "modelfile".to_string(),
+            reason: format!("line {}: {reason}", self.line),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    const MODELFILE: &str = r#"# Modelfile generated by "ollama show"
+FROM llama3.2:latest
+template """{{ if .System }}<|system|>
+{{ .System }}{{ end }}<|user|>
+{{ .Prompt }}"""
+PARAMETER temperature 0.7
+PARAMETER stop "<|user|>"
+PARAMETER stop <|end|>
+PARAMETER num_ctx 4096
+SYSTEM You are Mario from Super Mario Bros.
+
+MESSAGE user Who are you?
+MESSAGE assistant "It's-a me, \"Mario\"!"
+LICENSE """MIT License
+Copyright (c) Example"""
+"#;
+
+    #[test]
+    fn test_parse_instructions() {
+        let modelfile = Modelfile::parse(MODELFILE).unwrap();
+
+        assert_eq!(modelfile.instructions.len(), 11);
+        assert_eq!(
+            modelfile.instructions[0],
+            Instruction::Comment(" Modelfile generated by \"ollama show\"".to_string())
+        );
+        assert_eq!(modelfile.from(), Some("llama3.2:latest"));
+        assert!(modelfile.template().unwrap().starts_with("{{ if .System }}<|system|>\n"));
+        assert_eq!(modelfile.system(), Some("You are Mario from Super Mario Bros."));
+        assert_eq!(
+            modelfile.parameters().collect::<Vec<_>>()[1..3],
+            [("stop", "<|user|>"), ("stop", "<|end|>")]
+        );
+        assert_eq!(modelfile.messages()[1].role, MessageRole::Assistant);
+        assert_eq!(modelfile.messages()[1].content, "It's-a me, \"Mario\"!");
+        assert_eq!(
+            modelfile.licenses().collect::<Vec<_>>(),
+            ["MIT License\nCopyright (c) Example"]
+        );
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let modelfile = Modelfile::parse(MODELFILE).unwrap();
+        let written = modelfile.to_string();
+
+        assert_eq!(Modelfile::parse(&written).unwrap(), modelfile);
+        assert_eq!(written.parse::<Modelfile>().unwrap().to_string(), written);
+
+        let awkward = Modelfile {
+            instructions: vec![
+                Instruction::System("  padded  ".to_string()),
+                Instruction::Template("ends with \"quote\"".to_string()),
+                Instruction::License("has \"\"\" inside\\".to_string()),
+            ],
+        };
+        assert_eq!(Modelfile::parse(&awkward.to_string()).unwrap(), awkward);
+    }
+
+    #[test]
+    fn test_options_conversion() {
+        let mut modelfile = Modelfile::parse(MODELFILE).unwrap();
+        let options = modelfile.options().unwrap();
+
+        assert_eq!(options.temperature, Some(0.7));
+        assert_eq!(options.num_ctx, Some(4096));
+        assert_eq!(options.stop, Some(vec!["<|user|>".to_string(), "<|end|>".to_string()]));
+
+        modelfile.set_options(&Options { top_k: Some(20), ..options });
+        assert!(
+            matches!(&modelfile.instructions[3], Instruction::Parameter { name, .. } if name == "num_ctx")
+        );
+        assert_eq!(modelfile.options().unwrap().top_k, Some(20));
+        assert_eq!(modelfile.parameters().count(), 5);
+
+        let invalid = Modelfile::parse("FROM m\nPARAMETER num_ctx large").unwrap();
+        assert!(matches!(invalid.options(), Err(OllamaError::InvalidParameter { .. })));
+    }
+
+    #[test]
+    fn test_create_request_conversion() {
+        let request = Modelfile::parse(MODELFILE).unwrap().to_create_request("mario").unwrap();
+
+        assert_eq!(request.model, "mario");
+        assert_eq!(request.from.as_deref(), Some("llama3.2:latest"));
+        assert_eq!(request.parameters.unwrap().num_ctx, Some(4096));
+        assert_eq!(request.messages.len(), 2);
+        assert_eq!(request.license.len(), 1);
+
+        let local = Modelfile::parse("FROM ./weights.gguf").unwrap();
+        assert!(matches!(
+            local.to_create_request("m"),
+            Err(OllamaError::InvalidParameter { parameter, .. }) if parameter == "FROM"
+        ));
+    }
+
+    #[test]
+    fn test_parse_errors_name_the_line() {
+        let cases = [
+            ("FROM m\nRUN echo", "line 2: unknown instruction 'RUN'"),
+            ("FROM m\n\nSYSTEM \"\"\"open", "line 3: unterminated \"\"\" in SYSTEM"),
+            ("MESSAGE narrator hi", "line 1: unknown message role 'narrator'"),
+            ("FROM", "line 1: FROM is missing a value"),
+            ("SYSTEM \"a\" b", "line 1: unexpected text after SYSTEM value"),
+        ];
+        for (text, expected) in cases {
+            let error = Modelfile::parse(text).unwrap_err();
+            assert!(
+                matches!(&error, OllamaError::InvalidParameter { reason, .. } if reason == expected),
+                "{text:?}: {error}"
+            );
+        }
+    }
+}
@@ -0,0 +1,526 @@
+//! Multi-host client pool with model-aware routing and failover
+
+use crate::{
+    api::{chat::ChatApi, embeddings::EmbeddingsApi, generate::GenerateApi},
+    client::OllamaClient,
+    error::{OllamaError, Result},
+    models::{
+        chat::{ChatRequest, ChatResponse},
+        embedding::{EmbedRequest, EmbedResponse},
+        generation::{GenerateRequest, GenerateResponse},
+        model_name::ModelName,
+    },
+    streaming::stream::{ChatStream, GenerateStream},
+    utils::{auth::redact_url, breaker::CircuitStatus},
+};
+use futures_util::{StreamExt, future::join_all};
+use std::{
+    collections::HashSet,
+    future::Future,
+    sync::{
+        Arc, RwLock,
+        atomic::{AtomicUsize, Ordering},
+    },
+    time::Duration,
+};
+use url::Url;
+
+/// Snapshot of one host in an [`OllamaPool`]
+#[derive(Debug, Clone)]
+pub struct HostStatus {
+    /// Base URL of the host's client
+    pub base_url: Url,
+    /// Whether the host passed its last health check and has not failed since
+    pub healthy: bool,
+    /// Models installed on the host, as of the last refresh
+    pub models: HashSet<ModelName>,
+    /// Models loaded in memory, as of the last refresh or a successful request
+    pub loaded: HashSet<ModelName>,
+    /// Requests and streams the pool currently has open on the host
+    pub in_flight: usize,
+    /// Circuit breaker state of the host's endpoints
+    pub circuits: Vec<CircuitStatus>,
+}
+
+/// Routes requests across several Ollama hosts
+///
+/// Each request goes to a healthy host that already has the model loaded,
+/// then to one that has it installed, then to the least-loaded healthy host.
+/// Ties go to the host with the fewest requests in flight, and then to the
+/// host added first. When a host fails with a retryable error, it is marked
+/// unhealthy and the request moves on to the next host; a host that reports
+/// the model as missing or still loading is skipped the same way but stays
+/// healthy. Hosts marked unhealthy are
+/// only tried after all others, and become healthy again on the next
+/// successful request or [`refresh`](Self::refresh).
+///
+/// Clones share host state.
+#[derive(Debug, Clone)]
+pub struct OllamaPool {
+    hosts: Arc<[Arc<PoolHost>]>,
+}
+
+#[derive(Debug)]
+struct PoolHost {
+    client: OllamaClient,
+    in_flight: AtomicUsize,
+    state: RwLock<HostState>,
+}
+
+#[derive(Debug)]
+struct HostState {
+    healthy: bool,
+    models: HashSet<ModelName>,
+    loaded: HashSet<ModelName>,
+}
+
+/// Counts a request against its host until dropped
+struct InFlight(Arc<PoolHost>);
+
+impl OllamaPool {
+    /// Create a pool over the given clients
+    ///
+    /// Hosts start out healthy with no known models; call
+    /// [`refresh`](Self::refresh) or [`spawn_refresh`](Self::spawn_refresh) to
+    /// learn their state.
+    pub fn new<I: IntoIterator<Item = OllamaClient>>(clients: I) -> Result<Self> {
+        let hosts: Arc<[Arc<PoolHost>]> = clients
+            .into_iter()
+            .map(|client| {
+                Arc::new(PoolHost {
+                    client,
+                    in_flight: AtomicUsize::new(0),
+                    state: RwLock::new(HostState {
+                        healthy: true,
+                        models: HashSet::new(),
+                        loaded: HashSet::new(),
+                    }),
+                })
+            })
+            .collect();
+
+        if hosts.is_empty() {
+            return Err(OllamaError::ConfigError("OllamaPool needs at least one host".to_string()));
+        }
+        Ok(Self { hosts })
+    }
+
+    /// Create a pool with a default client for each base URL
+    pub fn from_urls<I, U>(urls: I) -> Result<Self>
+    where
+        I: IntoIterator<Item = U>,
+        U: AsRef<str>,
+    {
+        let clients = urls.into_iter().map(OllamaClient::new).collect::<Result<Vec<_>>>()?;
+        Self::new(clients)
+    }
+
+    /// Health-check every host and reload its installed and loaded models
+    pub async fn refresh(&self) {
+        join_all(self.hosts.iter().map(|host| host.refresh())).await;
+    }
+
+    /// Refresh the pool in the background every `interval`
+    ///
+    /// The task stops once every clone of the pool has been dropped.
+    pub fn spawn_refresh(&self, interval: Duration) -> tokio::task::JoinHandle<()> {
+        let hosts = Arc::downgrade(&self.hosts);
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            loop {
+                ticker.tick().await;
+                let Some(hosts) = hosts.upgrade() else { break };
+                Self { hosts }.refresh().await;
+            }
+        })
+    }
+
+    /// Get the current state of every host, in the order they were added
+    pub fn hosts(&self) -> Vec<HostStatus> {
+        self.hosts
+            .iter()
+            .map(|host| {
+                let state = host.state.read().expect("pool lock poisoned");
+                HostStatus {
+                    base_url: host.client.config().base_url.clone(),
+                    healthy: state.healthy,
+                    models: state.models.clone(),
+                    loaded: state.loaded.clone(),
+                    in_flight: host.in_flight.load(Ordering::Relaxed),
+                    circuits: host.client.circuit_status(),
+                }
+            })
+            .collect()
+    }
+
+    /// Get the client the pool would route a request for `model` to first
+    ///
+    /// Use this for operations the pool does not route itself. Requests sent
+    /// through the returned client do not fail over. An invalid model name is
+    /// routed like a model no host has.
+    pub fn client_for(&self, model: &str) -> OllamaClient {
+        self.candidates(ModelName::parse(model).ok().as_ref())[0].client.clone()
+    }
+
+    /// Generate a completion (non-streaming) on the best host for the model
+    pub async fn generate(&self, request: GenerateRequest) -> Result<GenerateResponse> {
+        let model = request.model.clone();
+        self.route(&model, |host| {
+            let request = request.clone();
+            async move { GenerateApi::generate(host.client().http_client(), request).await }
+        })
+        .await
+    }
+
+    /// Generate a streaming completion on the best host for the model
+    ///
+    /// Failover only happens before the stream is returned.
+    pub async fn generate_stream(&self, request: GenerateRequest) -> Result<GenerateStream> {
+        let model = request.model.clone();
+        self.route(&model, |host| {
+            let request = request.clone();
+            async move {
+                let stream =
+                    GenerateApi::generate_stream(host.client().http_client(), request).await?;
+                Ok(GenerateStream::new(Box::pin(host.hold_during(stream))))
+            }
+        })
+        .await
+    }
+
+    /// Send a chat request (non-streaming) to the best host for the model
+    pub async fn chat(&self, request: ChatRequest) -> Result<ChatResponse> {
+        let model = request.model.clone();
+        self.route(&model, |host| {
+            let request = request.clone();
+            async move { ChatApi::chat(host.client().http_client(), request).await }
+        })
+        .await
+    }
+
+    /// Send a streaming chat request to the best host for the model
+    ///
+    /// Failover only happens before the stream is returned.
+    pub async fn chat_stream(&self, request: ChatRequest) -> Result<ChatStream> {
+        let model = request.model.clone();
+        self.route(&model, |host| {
+            let request = request.clone();
+            async move {
+                let stream = ChatApi::chat_stream(host.client().http_client(), request).await?;
+                Ok(ChatStream::new(Box::pin(host.hold_during(stream))))
+            }
+        })
+        .await
+    }
+
+    /// Generate embeddings on the best host for the model
+    pub async fn embed(&self, request: EmbedRequest) -> Result<EmbedResponse> {
+        let model = request.model.clone();
+        self.route(&model, |host| {
+            let request = request.clone();
+            async move { EmbeddingsApi::embed(host.client().http_client(), request).await }
+        })
+        .await
+    }
+
+    /// Hosts in the order a request for `model` should try them
+    fn candidates(&self, model: Option<&ModelName>) -> Vec<Arc<PoolHost>> {
+        let mut ranked: Vec<_> = self
+            .hosts
+            .iter()
+            .map(|host| ((host.rank(model), host.in_flight.load(Ordering::Relaxed)), host.clone()))
+            .collect();
+        // Stable, so ties keep the order hosts were added in
+        ranked.sort_by_key(|(key, _)| *key);
+        ranked.into_iter().map(|(_, host)| host).collect()
+    }
+
+    /// Send a request to each candidate host in turn until one does not fail over
+    async fn route<T, F, Fut>(&self, model: &str, send: F) -> Result<T>
+    where
+        F: Fn(InFlight) -> Fut,
+        Fut: Future<Output = Result<T>>,
+    {
+        let model = ModelName::parse(model)?;
+        let mut last_error = None;
+
+        for host in self.candidates(Some(&model)) {
+            let error = match send(InFlight::new(host.clone())).await {
+                Ok(value) => {
+                    host.record_success(&model);
+                    return Ok(value);
+                }
+                // The host is working, just not ready to serve this model yet
+                Err(error @ OllamaError::ModelLoading(..)) => error,
+                Err(error) if error.is_retryable() => {
+                    host.state.write().expect("pool lock poisoned").healthy = false;
+                    error
+                }
+                Err(error @ OllamaError::ModelNotFound(..)) => {
+                    let mut state = host.state.write().expect("pool lock poisoned");
+                    state.models.remove(&model);
+                    state.loaded.remove(&model);
+                    error
+                }
+                Err(error) => return Err(error),
+            };
+
+            log::warn!(
+                "Ollama host {} failed for model {model}, trying the next host: {error}",
+                redact_url(&host.client.config().base_url)
+            );
+            last_error = Some(error);
+        }
+
+        Err(last_error.expect("pool has at least one host"))
+    }
+}
+
+impl PoolHost {
+    /// Sort key for routing: loaded, then installed, then unknown, then unhealthy
+    fn rank(&self, model: Option<&ModelName>) -> u8 {
+        let state = self.state.read().expect("pool lock poisoned");
+        if !state.healthy {
+            3
+        } else if model.is_some_and(|model| state.loaded.contains(model)) {
+            0
+        } else if model.is_some_and(|model| state.models.contains(model)) {
+            1
+        } else {
+            2
+        }
+    }
+
+    fn record_success(&self, model: &ModelName) {
+        let mut state = self.state.write().expect("pool lock poisoned");
+        state.healthy = true;
+        state.models.insert(model.clone());
+        state.loaded.insert(model.clone());
+    }
+
+    async fn refresh(&self) {
+        let healthy = self.client.health().await.unwrap_or(false);
+        let (models, loaded) = if healthy {
+            let (models, loaded) =
+                tokio::join!(self.client.list_models(), self.client.list_running_models());
+            (models.ok(), loaded.ok())
+        } else {
+            (None, None)
+        };
+
+        let mut state = self.state.write().expect("pool lock poisoned");
+        state.healthy = healthy;
+        if let Some(models) = models {
+            state.models =
+                models.models.iter().filter_map(|m| ModelName::parse(&m.name).ok()).collect();
+        }
+        match loaded {
+            Some(loaded) => {
+                state.loaded =
+                    loaded.models.iter().filter_map(|m| ModelName::parse(&m.name).ok()).collect();
+            }
+            None if !healthy => state.loaded.clear(),
+            None => {}
+        }
+    }
+}
+
+impl InFlight {
+    fn new(host: Arc<PoolHost>) -> Self {
+        host.in_flight.fetch_add(1, Ordering::Relaxed);
+        Self(host)
+    }
+
+    fn client(&self) -> &OllamaClient {
+        &self.0.client
+    }
+
+    /// Keep counting the request until the stream is finished or dropped
+    fn hold_during<S: futures_util::Stream + Send>(
+        self,
+        stream: S,
+    ) -> impl futures_util::Stream<Item = S::Item> + Send {
+        stream.map(move |item| {
+            let _held = &self;
+            item
+        })
+    }
+}
+
+impl Drop for InFlight {
+    fn drop(&mut self) {
+        self.0.in_flight.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ClientConfig;
+    use serde_json::json;
+    use wiremock::{
+        Mock, MockServer, ResponseTemplate,
+        matchers::{method, path},
+    };
+
+    fn client(uri: String) -> OllamaClient {
+        let config = ClientConfig::builder().base_url(uri).max_retries(0).build().unwrap();
+        OllamaClient::with_config(config).unwrap()
+    }
+
+    async fn host(installed: &[&str], loaded: &[&str]) -> MockServer {
+        let server = MockServer::start().await;
+        let model = |name: &&str| json!({"name": name, "size": 1, "digest": "sha256:0"});
+
+        Mock::given(method("GET"))
+            .and(path("/"))
+            .respond_with(ResponseTemplate::new(200))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/tags"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(
+                    json!({"models": installed.iter().map(model).collect::<Vec<_>>()}),
+                ),
+            )
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/ps"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(json!({"models": loaded.iter().map(model).collect::<Vec<_>>()})),
+            )
+            .mount(&server)
+            .await;
+        server
+    }
+
+    async fn respond_to_generate(server: &MockServer, status: u16, expected: u64) {
+        Mock::given(method("POST"))
+            .and(path("/api/generate"))
+            .respond_with(
+                ResponseTemplate::new(status)
+                    .set_body_json(json!({"model": "llama3", "response": "hi", "done": true})),
+            )
+            .expect(expected)
+            .mount(server)
+            .await;
+    }
+
+    fn generate_request() -> GenerateRequest {
+        GenerateRequest { model: "llama3".to_string(), ..Default::default() }
+    }
+
+    fn name(name: &str) -> ModelName {
+        ModelName::parse(name).unwrap()
+    }
+
+    #[test]
+    fn test_pool_needs_a_host() {
+        assert!(OllamaPool::new(Vec::new()).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_routes_to_host_with_model_loaded() {
+        let (idle, installed, loaded) = (
+            host(&[], &[]).await,
+            host(&["library/llama3:latest"], &[]).await,
+            host(&[], &["LLaMA3"]).await,
+        );
+        respond_to_generate(&idle, 200, 0).await;
+        respond_to_generate(&installed, 200, 0).await;
+        respond_to_generate(&loaded, 200, 1).await;
+
+        let pool = OllamaPool::new([&idle, &installed, &loaded].map(|server| client(server.uri())))
+            .unwrap();
+        pool.refresh().await;
+
+        let response = pool.generate(generate_request()).await.unwrap();
+        assert_eq!(response.response, "hi");
+        assert_eq!(
+            pool.client_for("llama3").config().base_url.as_str(),
+            format!("{}/", loaded.uri())
+        );
+        assert!(pool.hosts()[1].models.contains(&name("llama3")));
+    }
+
+    #[tokio::test]
+    async fn test_fails_over_on_retryable_error() {
+        let (failing, healthy) = (host(&[], &["llama3"]).await, host(&[], &[]).await);
+        respond_to_generate(&failing, 503, 1).await;
+        respond_to_generate(&healthy, 200, 2).await;
+
+        let pool = OllamaPool::new([client(failing.uri()), client(healthy.uri())]).unwrap();
+        pool.refresh().await;
+
+        pool.generate(generate_request()).await.unwrap();
+        let hosts = pool.hosts();
+        assert!(!hosts[0].healthy);
+        assert!(hosts[1].loaded.contains(&name("llama3")));
+
+        // The unhealthy host is skipped until it recovers
+        pool.generate(generate_request()).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_fails_over_without_marking_loading_host_unhealthy() {
+        let (loading, ready) = (host(&["llama3"], &[]).await, host(&[], &[]).await);
+        Mock::given(method("POST"))
+            .and(path("/api/generate"))
+            .respond_with(
+                ResponseTemplate::new(503).set_body_json(json!({"error": "model is loading"})),
+            )
+            .mount(&loading)
+            .await;
+        respond_to_generate(&ready, 200, 1).await;
+
+        let pool = OllamaPool::new([client(loading.uri()), client(ready.uri())]).unwrap();
+        pool.refresh().await;
+
+        pool.generate(generate_request()).await.unwrap();
+        let hosts = pool.hosts();
+        assert!(hosts[0].healthy);
+        assert!(hosts[1].loaded.contains(&name("llama3")));
+    }
+
+    #[tokio::test]
+    async fn test_does_not_fail_over_on_client_error() {
+        let (first, second) = (host(&[], &[]).await, host(&[], &[]).await);
+        respond_to_generate(&first, 400, 1).await;
+        respond_to_generate(&second, 200, 0).await;
+
+        let pool = OllamaPool::new([client(first.uri()), client(second.uri())]).unwrap();
+        let error = pool.generate(generate_request()).await.unwrap_err();
+
+        assert_eq!(error.status_code(), Some(400));
+    }
+
+    #[tokio::test]
+    async fn test_refresh_marks_unreachable_host_unhealthy() {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        drop(listener);
+
+        let reachable = host(&["llama3"], &[]).await;
+        let pool = OllamaPool::new([client(format!("http://{address}")), client(reachable.uri())])
+            .unwrap();
+        pool.refresh().await;
+
+        let hosts = pool.hosts();
+        assert!(!hosts[0].healthy && hosts[1].healthy);
+        assert_eq!(pool.client_for("llama3").config().base_url, hosts[1].base_url);
+    }
+
+    #[tokio::test]
+    async fn test_prefers_least_loaded_host() {
+        let pool = OllamaPool::from_urls(["http://a.invalid", "http://b.invalid"]).unwrap();
+        let busy = InFlight::new(pool.hosts[0].clone());
+
+        assert_eq!(pool.client_for("llama3").config().base_url.as_str(), "http://b.invalid/");
+        assert_eq!(pool.hosts()[0].in_flight, 1);
+        drop(busy);
+        assert_eq!(pool.client_for("llama3").config().base_url.as_str(), "http://a.invalid/");
+    }
+}
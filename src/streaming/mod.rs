@@ -1,3 +1,6 @@
 //! Streaming utilities
 
+pub mod ndjson;
+pub mod progress;
 pub mod stream;
+pub mod timeout;
@@ -0,0 +1,295 @@
+//! Incremental newline-delimited JSON decoding
+//!
+//! Ollama streams one JSON record per line, but the transport is free to split a
+//! record across several chunks or to coalesce several records into one chunk.
+//! [`NdjsonDecoder`] buffers bytes until a full line is available so that every
+//! streaming endpoint yields exactly one item per record.
+
+use crate::error::{OllamaError, Result};
+use futures_util::{Stream, StreamExt, stream};
+use serde::{Deserialize, de::DeserializeOwned};
+use std::pin::Pin;
+
+/// Buffered decoder that splits a byte stream into NDJSON records
+#[derive(Debug, Default)]
+pub struct NdjsonDecoder {
+    buffer: Vec<u8>,
+    /// Offset up to which the buffer is known not to contain a newline
+    scanned: usize,
+}
+
+impl NdjsonDecoder {
+    /// Create a new, empty decoder
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append bytes received from the transport
+    pub fn push(&mut self, bytes: &[u8]) {
+        self.buffer.extend_from_slice(bytes);
+    }
+
+    /// Take the next complete, non-blank record if one is buffered
+    pub fn next_record(&mut self) -> Option<Vec<u8>> {
+        loop {
+            let newline = self.buffer[self.scanned..].iter().position(|&b| b == b'\n')?;
+            let end = self.scanned + newline;
+            let mut line: Vec<u8> = self.buffer.drain(..=end).collect();
+            self.scanned = 0;
+
+            line.pop();
+            if let Some(record) = trim_record(line) {
+                return Some(record);
+            }
+        }
+    }
+
+    /// Take any trailing record that was not terminated by a newline
+    pub fn finish(&mut self) -> Option<Vec<u8>> {
+        self.scanned = 0;
+        trim_record(std::mem::take(&mut self.buffer))
+    }
+
+    /// Number of buffered bytes that do not yet form a complete record
+    pub fn pending(&self) -> usize {
+        self.buffer.len()
+    }
+
+    fn mark_scanned(&mut self) {
+        self.scanned = self.buffer.len();
+    }
+}
+
+fn trim_record(mut line: Vec<u8>) -> Option<Vec<u8>> {
+    while line.last().is_some_and(u8::is_ascii_whitespace) {
+        line.pop();
+    }
+    let start = line.iter().position(|b| !b.is_ascii_whitespace())?;
+    line.drain(..start);
+    Some(line)
+}
+
+/// Parse a single NDJSON record into a typed item
+///
+/// A record with an `error` field is the server reporting a failure after the
+/// stream started and becomes [`OllamaError::StreamError`], whatever its
+/// whitespace or other fields.
+fn parse_record<T: DeserializeOwned>(record: &[u8]) -> Result<T> {
+    let item = serde_json::from_slice(record);
+    // Typed records that parsed only need a second look if they could hold an `error` key
+    if (item.is_err() || record.windows(7).any(|window| window == b"\"error\""))
+        && let Some(error) = error_message(record)
+    {
+        return Err(OllamaError::StreamError(error, None));
+    }
+
+    item.map_err(|e| {
+        OllamaError::InvalidResponse(
+            format!("Failed to parse chunk: {e} - Line: {}", String::from_utf8_lossy(record)),
+            None,
+        )
+    })
+}
+
+/// The `error` field of a record, if it has one
+fn error_message(record: &[u8]) -> Option<String> {
+    #[derive(Deserialize)]
+    struct ErrorRecord {
+        error: String,
+    }
+
+    serde_json::from_slice(record).ok().map(|ErrorRecord { error }| error)
+}
+
+struct DecodeState<S> {
+    inner: Pin<Box<S>>,
+    decoder: NdjsonDecoder,
+    finished: bool,
+}
+
+/// Decode a stream of byte chunks into one typed item per NDJSON record
+///
+/// Transport errors are converted into [`OllamaError`], yielded, and end the
+/// stream. Records that fail to deserialize are reported as
+/// [`OllamaError::InvalidResponse`] without affecting subsequent records.
+pub fn decode_ndjson<T, S, B, E>(
+    chunks: S,
+) -> impl Stream<Item = Result<T>> + Send + use<T, S, B, E>
+where
+    T: DeserializeOwned + Send,
+    S: Stream<Item = std::result::Result<B, E>> + Send,
+    B: AsRef<[u8]>,
+    E: Into<OllamaError>,
+{
+    let state =
+        DecodeState { inner: Box::pin(chunks), decoder: NdjsonDecoder::new(), finished: false };
+
+    stream::unfold(state, |mut state| async move {
+        loop {
+            if let Some(record) = state.decoder.next_record() {
+                return Some((parse_record(&record), state));
+            }
+            if state.finished {
+                let record = state.decoder.finish()?;
+                return Some((parse_record(&record), state));
+            }
+
+            state.decoder.mark_scanned();
+            match state.inner.next().await {
+                Some(Ok(bytes)) => state.decoder.push(bytes.as_ref()),
+                Some(Err(e)) => {
+                    state.finished = true;
+                    state.decoder = NdjsonDecoder::new();
+                    return Some((Err(e.into()), state));
+                }
+                None => state.finished = true,
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Record {
+        n: u32,
+    }
+
+    const BODY: &[u8] = b"{\"n\":1}\n{\"n\":2}\n\n{\"n\":3}\r\n{\"n\":4}";
+
+    async fn collect(chunks: Vec<Vec<u8>>) -> Vec<Result<Record>> {
+        let chunks = stream::iter(chunks.into_iter().map(Ok::<_, std::io::Error>));
+        decode_ndjson::<Record, _, _, _>(chunks).collect().await
+    }
+
+    fn numbers(items: Vec<Result<Record>>) -> Vec<u32> {
+        items.into_iter().map(|item| item.unwrap().n).collect()
+    }
+
+    #[test]
+    fn test_decoder_splits_lines() {
+        let mut decoder = NdjsonDecoder::new();
+        decoder.push(b"{\"a\":1}\n{\"b\"");
+
+        assert_eq!(decoder.next_record(), Some(b"{\"a\":1}".to_vec()));
+        assert_eq!(decoder.next_record(), None);
+        assert_eq!(decoder.pending(), 4);
+
+        decoder.push(b":2}\n");
+        assert_eq!(decoder.next_record(), Some(b"{\"b\":2}".to_vec()));
+        assert_eq!(decoder.finish(), None);
+    }
+
+    #[test]
+    fn test_decoder_skips_blank_lines_and_trims() {
+        let mut decoder = NdjsonDecoder::new();
+        decoder.push(b"\n  \r\n {\"a\":1} \r\n");
+
+        assert_eq!(decoder.next_record(), Some(b"{\"a\":1}".to_vec()));
+        assert_eq!(decoder.next_record(), None);
+    }
+
+    #[tokio::test]
+    async fn test_decode_single_chunk() {
+        let items = collect(vec![BODY.to_vec()]).await;
+        assert_eq!(numbers(items), vec![1, 2, 3, 4]);
+    }
+
+    #[tokio::test]
+    async fn test_decode_byte_by_byte() {
+        let chunks = BODY.iter().map(|b| vec![*b]).collect();
+        let items = collect(chunks).await;
+        assert_eq!(numbers(items), vec![1, 2, 3, 4]);
+    }
+
+    #[tokio::test]
+    async fn test_decode_uneven_chunks() {
+        let chunks = BODY.chunks(5).map(<[u8]>::to_vec).collect();
+        let items = collect(chunks).await;
+        assert_eq!(numbers(items), vec![1, 2, 3, 4]);
+    }
+
+    #[tokio::test]
+    async fn test_decode_multibyte_utf8_split() {
+        #[derive(Debug, Deserialize)]
+        struct Text {
+            text: String,
+        }
+
+        let body = "{\"text\":\"héllo ✓\"}\n".as_bytes();
+        let chunks = stream::iter(body.iter().map(|b| Ok::<_, std::io::Error>(vec![*b])));
+        let items: Vec<Result<Text>> = decode_ndjson(chunks).collect().await;
+
+        assert_eq!(items.len(), 1);
+        assert_eq!(items.into_iter().next().unwrap().unwrap().text, "héllo ✓");
+    }
+
+    #[tokio::test]
+    async fn test_decode_invalid_record_does_not_stop_stream() {
+        let items = collect(vec![b"{\"n\":1}\nnot json\n{\"n\":2}\n".to_vec()]).await;
+
+        assert_eq!(items.len(), 3);
+        assert!(matches!(items[1], Err(OllamaError::InvalidResponse(..))));
+        assert_eq!(items[2].as_ref().unwrap().n, 2);
+    }
+
+    #[tokio::test]
+    async fn test_decode_transport_error_ends_stream() {
+        let chunks = stream::iter(vec![
+            Ok(b"{\"n\":1}\n{\"n\"".to_vec()),
+            Err(OllamaError::StreamError("connection reset".to_string(), None)),
+            Ok(b":2}\n".to_vec()),
+        ]);
+        let items: Vec<Result<Record>> = decode_ndjson(chunks).collect().await;
+
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0].as_ref().unwrap().n, 1);
+        assert!(
+            matches!(&items[1], Err(OllamaError::StreamError(msg, _)) if msg == "connection reset")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_decode_empty_stream() {
+        let items = collect(Vec::new()).await;
+        assert!(items.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_error_records_become_stream_errors() {
+        let body: &[u8] = b"{\"n\":1}\n{\"error\":\"pull model manifest: file does not exist\"}\n";
+        let chunks = stream::iter([Ok::<_, std::io::Error>(body)]);
+        let items: Vec<Result<serde_json::Value>> = decode_ndjson(chunks).collect().await;
+
+        assert!(items[0].is_ok());
+        assert!(matches!(
+            &items[1],
+            Err(OllamaError::StreamError(message, _)) if message == "pull model manifest: file does not exist"
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_error_records_with_spacing_or_other_fields() {
+        #[derive(Debug, Deserialize)]
+        struct Status {
+            status: String,
+        }
+
+        let body: &[u8] =
+            b"{ \"error\" : \"disk full\" }\n{\"status\":\"pulling\",\"error\":\"reset\"}\n\
+            {\"status\":\"error\"}\n";
+        let chunks = stream::iter([Ok::<_, std::io::Error>(body)]);
+        let items: Vec<Result<Status>> = decode_ndjson(chunks).collect().await;
+
+        assert!(
+            matches!(&items[0], Err(OllamaError::StreamError(message, _)) if message == "disk full")
+        );
+        assert!(
+            matches!(&items[1], Err(OllamaError::StreamError(message, _)) if message == "reset")
+        );
+        assert_eq!(items[2].as_ref().unwrap().status, "error");
+    }
+}
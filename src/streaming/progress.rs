@@ -0,0 +1,307 @@
+//! Aggregate progress tracking for pull, push, and create streams
+
+use crate::{
+    error::OllamaError,
+    models::model_info::{CreateProgress, PullProgress, PushProgress},
+};
+use std::{collections::VecDeque, time::Duration};
+use tokio::time::Instant;
+
+/// Window over which throughput is averaged
+const THROUGHPUT_WINDOW: Duration = Duration::from_secs(5);
+
+/// A progress record from a pull, push, or create stream
+pub trait ProgressRecord {
+    /// Status message, such as `pulling manifest` or `success`
+    fn status(&self) -> &str;
+    /// Digest of the layer being transferred
+    fn digest(&self) -> Option<&str>;
+    /// Size of the layer in bytes
+    fn total(&self) -> Option<u64>;
+    /// Bytes of the layer transferred so far
+    fn completed(&self) -> Option<u64>;
+}
+
+impl ProgressRecord for PullProgress {
+    fn status(&self) -> &str {
+        &self.status
+    }
+
+    fn digest(&self) -> Option<&str> {
+        self.digest.as_deref()
+    }
+
+    fn total(&self) -> Option<u64> {
+        self.total
+    }
+
+    fn completed(&self) -> Option<u64> {
+        self.completed
+    }
+}
+
+impl ProgressRecord for PushProgress {
+    fn status(&self) -> &str {
+        &self.status
+    }
+
+    fn digest(&self) -> Option<&str> {
+        self.digest.as_deref()
+    }
+
+    fn total(&self) -> Option<u64> {
+        self.total
+    }
+
+    fn completed(&self) -> Option<u64> {
+        self.completed
+    }
+}
+
+impl ProgressRecord for CreateProgress {
+    fn status(&self) -> &str {
+        &self.status
+    }
+
+    fn digest(&self) -> Option<&str> {
+        self.digest.as_deref()
+    }
+
+    fn total(&self) -> Option<u64> {
+        self.total
+    }
+
+    fn completed(&self) -> Option<u64> {
+        self.completed
+    }
+}
+
+/// Transfer progress of one layer
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LayerProgress {
+    /// Layer digest
+    pub digest: String,
+    /// Layer size in bytes
+    pub total: u64,
+    /// Bytes transferred so far
+    pub completed: u64,
+}
+
+/// Where a tracked operation stands
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ProgressState {
+    /// Records are still arriving
+    Running,
+    /// The server sent the terminal `success` record
+    Succeeded,
+    /// The stream yielded an error
+    Failed(String),
+}
+
+/// Aggregates pull, push, or create progress across every layer
+///
+/// Feed each stream item to [`update`](Self::update), or errors to
+/// [`fail`](Self::fail), then read totals, throughput and ETA for a single
+/// progress bar. Layers are added as the server announces them, so the
+/// total grows until every layer has been seen.
+#[derive(Debug, Clone)]
+pub struct ProgressTracker {
+    layers: Vec<LayerProgress>,
+    status: String,
+    state: ProgressState,
+    samples: VecDeque<(Instant, u64)>,
+}
+
+impl Default for ProgressTracker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ProgressTracker {
+    /// Create an empty tracker
+    pub fn new() -> Self {
+        Self {
+            layers: Vec::new(),
+            status: String::new(),
+            state: ProgressState::Running,
+            samples: VecDeque::new(),
+        }
+    }
+
+    /// Record a progress update
+    pub fn update(&mut self, record: &impl ProgressRecord) {
+        self.status = record.status().to_string();
+        if record.status() == "success" {
+            self.state = ProgressState::Succeeded;
+        }
+
+        let Some(digest) = record.digest() else {
+            return;
+        };
+        let index = match self.layers.iter().position(|layer| layer.digest == digest) {
+            Some(index) => index,
+            None => {
+                self.layers.push(LayerProgress {
+                    digest: digest.to_string(),
+                    total: 0,
+                    completed: 0,
+                });
+                self.layers.len() - 1
+            }
+        };
+        let layer = &mut self.layers[index];
+        layer.total = record.total().unwrap_or(layer.total);
+        layer.completed = record.completed().unwrap_or(layer.completed);
+
+        let now = Instant::now();
+        let completed = self.completed_bytes();
+        self.samples.push_back((now, completed));
+        while self.samples.len() > 2
+            && self.samples.front().is_some_and(|(at, _)| now - *at > THROUGHPUT_WINDOW)
+        {
+            self.samples.pop_front();
+        }
+    }
+
+    /// Record that the stream failed
+    pub fn fail(&mut self, error: &OllamaError) {
+        self.state = ProgressState::Failed(error.to_string());
+    }
+
+    /// Where the operation stands
+    pub fn state(&self) -> &ProgressState {
+        &self.state
+    }
+
+    /// Whether the operation succeeded or failed
+    pub fn is_finished(&self) -> bool {
+        self.state != ProgressState::Running
+    }
+
+    /// Latest status message
+    pub fn status(&self) -> &str {
+        &self.status
+    }
+
+    /// Every layer seen so far, in the order the server announced them
+    pub fn layers(&self) -> &[LayerProgress] {
+        &self.layers
+    }
+
+    /// Total size of every layer seen so far, in bytes
+    pub fn total_bytes(&self) -> u64 {
+        self.layers.iter().map(|layer| layer.total).sum()
+    }
+
+    /// Bytes transferred across every layer
+    pub fn completed_bytes(&self) -> u64 {
+        self.layers.iter().map(|layer| layer.completed).sum()
+    }
+
+    /// Fraction of known bytes transferred, from 0.0 to 1.0
+    pub fn fraction(&self) -> Option<f64> {
+        match self.total_bytes() {
+            0 if self.state == ProgressState::Succeeded => Some(1.0),
+            0 => None,
+            total => Some(self.completed_bytes() as f64 / total as f64),
+        }
+    }
+
+    /// Recent transfer rate in bytes per second
+    pub fn throughput(&self) -> Option<f64> {
+        let (first_at, first) = self.samples.front()?;
+        let (last_at, last) = self.samples.back()?;
+        let elapsed = (*last_at - *first_at).as_secs_f64();
+        (elapsed > 0.0).then(|| last.saturating_sub(*first) as f64 / elapsed)
+    }
+
+    /// Estimated time until the known layers finish, at the recent throughput
+    pub fn eta(&self) -> Option<Duration> {
+        let remaining = self.total_bytes().saturating_sub(self.completed_bytes());
+        match self.throughput()? {
+            _ if remaining == 0 => Some(Duration::ZERO),
+            rate if rate > 0.0 => Some(Duration::from_secs_f64(remaining as f64 / rate)),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn layer(digest: &str, total: u64, completed: u64) -> PullProgress {
+        PullProgress {
+            status: format!("pulling {digest}"),
+            digest: Some(digest.to_string()),
+            total: Some(total),
+            completed: Some(completed),
+        }
+    }
+
+    fn status(status: &str) -> PullProgress {
+        PullProgress { status: status.to_string(), digest: None, total: None, completed: None }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_aggregates_layers_throughput_and_eta() {
+        let mut tracker = ProgressTracker::new();
+        tracker.update(&status("pulling manifest"));
+        assert_eq!(tracker.fraction(), None);
+
+        tracker.update(&layer("sha256:a", 1000, 0));
+        tracker.update(&layer("sha256:b", 3000, 0));
+        tokio::time::advance(Duration::from_secs(1)).await;
+        tracker.update(&layer("sha256:a", 1000, 1000));
+        tokio::time::advance(Duration::from_secs(1)).await;
+        tracker.update(&layer("sha256:b", 3000, 1000));
+
+        assert_eq!(tracker.layers().len(), 2);
+        assert_eq!((tracker.completed_bytes(), tracker.total_bytes()), (2000, 4000));
+        assert_eq!(tracker.fraction(), Some(0.5));
+        assert_eq!(tracker.throughput(), Some(1000.0));
+        assert_eq!(tracker.eta(), Some(Duration::from_secs(2)));
+        assert_eq!(tracker.state(), &ProgressState::Running);
+
+        tracker.update(&status("verifying sha256 digest"));
+        tracker.update(&status("success"));
+        assert!(tracker.is_finished());
+        assert_eq!(tracker.status(), "success");
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_throughput_uses_recent_samples() {
+        let mut tracker = ProgressTracker::new();
+        tracker.update(&layer("sha256:a", 100_000, 0));
+        tokio::time::advance(Duration::from_secs(60)).await;
+        tracker.update(&layer("sha256:a", 100_000, 10_000));
+        for _ in 0..4 {
+            tokio::time::advance(Duration::from_secs(1)).await;
+            let completed = tracker.completed_bytes() + 5_000;
+            tracker.update(&layer("sha256:a", 100_000, completed));
+        }
+
+        assert_eq!(tracker.throughput(), Some(5_000.0));
+    }
+
+    #[test]
+    fn test_failure_and_create_records() {
+        let mut tracker = ProgressTracker::new();
+        tracker.update(&CreateProgress {
+            status: "using existing layer sha256:a".to_string(),
+            detail: None,
+            digest: Some("sha256:a".to_string()),
+            total: Some(10),
+            completed: Some(10),
+        });
+        assert_eq!(tracker.fraction(), Some(1.0));
+
+        tracker.fail(&OllamaError::StreamError("disk full".to_string(), None));
+        assert_eq!(
+            tracker.state(),
+            &ProgressState::Failed("Streaming error: disk full".to_string())
+        );
+        assert!(tracker.is_finished());
+    }
+}
@@ -27,15 +27,20 @@ impl GenerateStream {
     pub async fn collect_response(mut self) -> Result<GenerateResponse> {
         let mut final_response = None;
         let mut full_text = String::new();
+        let mut thinking: Option<String> = None;
 
         while let Some(chunk) = self.next().await {
             let response = chunk?;
             full_text.push_str(&response.response);
+            if let Some(chunk) = &response.thinking {
+                thinking.get_or_insert_default().push_str(chunk);
+            }
 
             if response.done {
                 final_response = Some(GenerateResponse {
                     model: response.model,
                     response: full_text,
+                    thinking,
                     done: true,
                     context: response.context,
                     total_duration: response.total_duration,
@@ -52,6 +57,7 @@ impl GenerateStream {
         final_response.ok_or_else(|| {
             crate::error::OllamaError::StreamError(
                 "Stream ended without final response".to_string(),
+                None,
             )
         })
     }
@@ -80,10 +86,14 @@ impl ChatStream {
     pub async fn collect_response(mut self) -> Result<ChatResponse> {
         let mut final_response = None;
         let mut full_content = String::new();
+        let mut thinking: Option<String> = None;
 
         while let Some(chunk) = self.next().await {
             let response = chunk?;
             full_content.push_str(&response.message.content);
+            if let Some(chunk) = &response.message.thinking {
+                thinking.get_or_insert_default().push_str(chunk);
+            }
 
             if response.done {
                 final_response = Some(ChatResponse {
@@ -91,6 +101,7 @@ impl ChatStream {
                     message: crate::models::chat::ChatMessage {
                         role: response.message.role,
                         content: full_content,
+                        thinking,
                         images: response.message.images,
                         tool_calls: response.message.tool_calls,
                         tool_call_id: response.message.tool_call_id,
@@ -110,6 +121,7 @@ impl ChatStream {
         final_response.ok_or_else(|| {
             crate::error::OllamaError::StreamError(
                 "Stream ended without final response".to_string(),
+                None,
             )
         })
     }
@@ -154,6 +166,7 @@ mod tests {
         let response = GenerateResponse {
             model: "test-model".to_string(),
             response: "Hello world".to_string(),
+            thinking: None,
             done: true,
             context: Some(vec![1, 2, 3]),
             total_duration: Some(1000),
@@ -181,6 +194,7 @@ mod tests {
         let chunk1 = GenerateResponse {
             model: "test-model".to_string(),
             response: "Hello".to_string(),
+            thinking: None,
             done: false,
             context: None,
             total_duration: None,
@@ -194,6 +208,7 @@ mod tests {
         let chunk2 = GenerateResponse {
             model: "test-model".to_string(),
             response: " world".to_string(),
+            thinking: None,
             done: false,
             context: None,
             total_duration: None,
@@ -207,6 +222,7 @@ mod tests {
         let final_chunk = GenerateResponse {
             model: "test-model".to_string(),
             response: "!".to_string(),
+            thinking: None,
             done: true,
             context: Some(vec![1, 2, 3]),
             total_duration: Some(1000),
@@ -235,6 +251,7 @@ mod tests {
         let chunk = GenerateResponse {
             model: "test-model".to_string(),
             response: "Hello".to_string(),
+            thinking: None,
             done: false,
             context: None,
             total_duration: None,
@@ -245,14 +262,14 @@ mod tests {
             eval_duration: None,
         };
 
-        let error = OllamaError::StreamError("Connection lost".to_string());
+        let error = OllamaError::StreamError("Connection lost".to_string(), None);
 
         let mock_stream = stream::iter(vec![Ok(chunk), Err(error)]);
         let generate_stream = GenerateStream::new(Box::pin(mock_stream));
 
         let result = generate_stream.collect_response().await;
         assert!(result.is_err());
-        assert!(matches!(result.unwrap_err(), OllamaError::StreamError(_)));
+        assert!(matches!(result.unwrap_err(), OllamaError::StreamError(..)));
     }
 
     #[tokio::test]
@@ -262,7 +279,7 @@ mod tests {
 
         let result = generate_stream.collect_response().await;
         assert!(result.is_err());
-        assert!(matches!(result.unwrap_err(), OllamaError::StreamError(_)));
+        assert!(matches!(result.unwrap_err(), OllamaError::StreamError(..)));
     }
 
     #[tokio::test]
@@ -272,6 +289,7 @@ mod tests {
             message: ChatMessage {
                 role: MessageRole::Assistant,
                 content: "Hello world".to_string(),
+                thinking: None,
                 images: None,
                 tool_calls: None,
                 tool_call_id: None,
@@ -303,6 +321,7 @@ mod tests {
             message: ChatMessage {
                 role: MessageRole::Assistant,
                 content: "Hello".to_string(),
+                thinking: None,
                 images: None,
                 tool_calls: None,
                 tool_call_id: None,
@@ -321,6 +340,7 @@ mod tests {
             message: ChatMessage {
                 role: MessageRole::Assistant,
                 content: " world".to_string(),
+                thinking: None,
                 images: Some(vec!["image1".to_string()]),
                 tool_calls: None,
                 tool_call_id: None,
@@ -339,6 +359,7 @@ mod tests {
             message: ChatMessage {
                 role: MessageRole::Assistant,
                 content: "!".to_string(),
+                thinking: None,
                 images: Some(vec!["image2".to_string()]),
                 tool_calls: None,
                 tool_call_id: Some("call123".to_string()),
@@ -372,6 +393,7 @@ mod tests {
             message: ChatMessage {
                 role: MessageRole::Assistant,
                 content: "Hello".to_string(),
+                thinking: None,
                 images: None,
                 tool_calls: None,
                 tool_call_id: None,
@@ -385,14 +407,14 @@ mod tests {
             eval_duration: None,
         };
 
-        let error = OllamaError::StreamError("Connection lost".to_string());
+        let error = OllamaError::StreamError("Connection lost".to_string(), None);
 
         let mock_stream = stream::iter(vec![Ok(chunk), Err(error)]);
         let chat_stream = ChatStream::new(Box::pin(mock_stream));
 
         let result = chat_stream.collect_response().await;
         assert!(result.is_err());
-        assert!(matches!(result.unwrap_err(), OllamaError::StreamError(_)));
+        assert!(matches!(result.unwrap_err(), OllamaError::StreamError(..)));
     }
 
     #[tokio::test]
@@ -402,7 +424,7 @@ mod tests {
 
         let result = chat_stream.collect_response().await;
         assert!(result.is_err());
-        assert!(matches!(result.unwrap_err(), OllamaError::StreamError(_)));
+        assert!(matches!(result.unwrap_err(), OllamaError::StreamError(..)));
     }
 
     #[tokio::test]
@@ -410,6 +432,7 @@ mod tests {
         let response1 = GenerateResponse {
             model: "test-model".to_string(),
             response: "chunk1".to_string(),
+            thinking: None,
             done: false,
             context: None,
             total_duration: None,
@@ -423,6 +446,7 @@ mod tests {
         let response2 = GenerateResponse {
             model: "test-model".to_string(),
             response: "chunk2".to_string(),
+            thinking: None,
             done: true,
             context: Some(vec![1, 2, 3]),
             total_duration: Some(1000),
@@ -455,6 +479,7 @@ mod tests {
             message: ChatMessage {
                 role: MessageRole::Assistant,
                 content: "chunk1".to_string(),
+                thinking: None,
                 images: None,
                 tool_calls: None,
                 tool_call_id: None,
@@ -473,6 +498,7 @@ mod tests {
             message: ChatMessage {
                 role: MessageRole::Assistant,
                 content: "chunk2".to_string(),
+                thinking: None,
                 images: None,
                 tool_calls: None,
                 tool_call_id: None,
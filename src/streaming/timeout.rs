@@ -0,0 +1,112 @@
+//! First-chunk and idle timeouts for streaming responses
+
+use crate::error::{OllamaError, Result};
+use futures_util::{Stream, StreamExt, stream};
+use std::{pin::Pin, time::Duration};
+use tokio::time::Instant;
+
+/// Deadlines that bound how long a stream may wait for data
+#[derive(Debug, Clone, Copy)]
+pub struct StreamTimeouts {
+    /// Instant by which the first item must arrive
+    pub first_chunk_deadline: Instant,
+    /// Configured first-chunk timeout, reported in the error
+    pub first_chunk: Duration,
+    /// Maximum time between consecutive items
+    pub idle: Duration,
+}
+
+struct TimeoutState<S> {
+    inner: Pin<Box<S>>,
+    timeouts: StreamTimeouts,
+    started: bool,
+}
+
+/// End a stream with a timeout error when it stalls
+///
+/// The first item must arrive before [`StreamTimeouts::first_chunk_deadline`],
+/// otherwise the stream yields [`OllamaError::FirstChunkTimeout`]. Every later
+/// item must follow the previous one within [`StreamTimeouts::idle`], otherwise
+/// it yields [`OllamaError::StreamIdleTimeout`]. The inner stream is dropped
+/// after a timeout so the connection is closed.
+pub fn with_stream_timeouts<S, T>(
+    inner: S,
+    timeouts: StreamTimeouts,
+) -> impl Stream<Item = Result<T>> + Send + use<S, T>
+where
+    S: Stream<Item = Result<T>> + Send,
+    T: Send,
+{
+    let state = TimeoutState { inner: Box::pin(inner), timeouts, started: false };
+
+    stream::unfold(Some(state), |state| async move {
+        let mut state = state?;
+        let (deadline, error) = if state.started {
+            (
+                Instant::now() + state.timeouts.idle,
+                OllamaError::StreamIdleTimeout(state.timeouts.idle, None),
+            )
+        } else {
+            (
+                state.timeouts.first_chunk_deadline,
+                OllamaError::FirstChunkTimeout(state.timeouts.first_chunk, None),
+            )
+        };
+
+        match tokio::time::timeout_at(deadline, state.inner.next()).await {
+            Ok(item) => {
+                state.started = true;
+                item.map(|item| (item, Some(state)))
+            }
+            Err(_) => Some((Err(error), None)),
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn timeouts(first_chunk: Duration, idle: Duration) -> StreamTimeouts {
+        StreamTimeouts { first_chunk_deadline: Instant::now() + first_chunk, first_chunk, idle }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_first_chunk_timeout() {
+        let inner = stream::pending::<Result<u32>>();
+        let items: Vec<_> =
+            with_stream_timeouts(inner, timeouts(Duration::from_secs(30), Duration::from_secs(1)))
+                .collect()
+                .await;
+
+        assert_eq!(items.len(), 1);
+        assert!(matches!(items[0], Err(OllamaError::FirstChunkTimeout(d, _)) if d.as_secs() == 30));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_idle_timeout_after_first_item() {
+        let inner = stream::iter(vec![Ok(1), Ok(2)]).chain(stream::pending());
+        let items: Vec<_> =
+            with_stream_timeouts(inner, timeouts(Duration::from_secs(30), Duration::from_secs(5)))
+                .collect()
+                .await;
+
+        assert_eq!(items.len(), 3);
+        assert_eq!(*items[1].as_ref().unwrap(), 2);
+        assert!(matches!(items[2], Err(OllamaError::StreamIdleTimeout(d, _)) if d.as_secs() == 5));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_slow_but_steady_stream_completes() {
+        let inner = stream::iter(1..=3).then(|n| async move {
+            tokio::time::sleep(Duration::from_secs(4)).await;
+            Ok(n)
+        });
+        let items: Vec<_> =
+            with_stream_timeouts(inner, timeouts(Duration::from_secs(5), Duration::from_secs(5)))
+                .collect()
+                .await;
+
+        assert_eq!(items.into_iter().map(Result::unwrap).collect::<Vec<_>>(), vec![1, 2, 3]);
+    }
+}
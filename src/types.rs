@@ -1,7 +1,8 @@
 //! Common types used throughout the SDK
 
+use crate::error::OllamaError;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::{cmp::Ordering, collections::HashMap, sync::Arc, time::Duration};
 
 /// HTTP method types
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -48,26 +49,299 @@ pub struct Progress {
     pub detail: Option<String>,
 }
 
-/// API version information
+/// Ollama server version
+///
+/// Parsed from strings such as `0.6.2`, `v0.5.0-rc1`, or `0.4.0+abc`.
+/// Versions compare by major, minor, patch, then pre-release, following
+/// semantic versioning; build metadata and the commit are ignored.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(try_from = "RawVersionInfo")]
 pub struct VersionInfo {
-    /// API version string
+    /// Version string as reported by the server
     pub version: String,
+    /// Major version
+    #[serde(skip_serializing)]
+    pub major: u64,
+    /// Minor version
+    #[serde(skip_serializing)]
+    pub minor: u64,
+    /// Patch version
+    #[serde(skip_serializing)]
+    pub patch: u64,
+    /// Pre-release identifiers, such as `rc1`
+    #[serde(skip_serializing)]
+    pub pre_release: Option<String>,
     /// Build information
     pub build: Option<String>,
     /// Git commit hash
     pub commit: Option<String>,
 }
 
-/// Health check response
+#[derive(Deserialize)]
+struct RawVersionInfo {
+    version: String,
+    build: Option<String>,
+    commit: Option<String>,
+}
+
+impl TryFrom<RawVersionInfo> for VersionInfo {
+    type Error = String;
+
+    fn try_from(raw: RawVersionInfo) -> std::result::Result<Self, Self::Error> {
+        let mut info = Self::parse(&raw.version)?;
+        info.build = raw.build.or(info.build);
+        info.commit = raw.commit;
+        Ok(info)
+    }
+}
+
+impl VersionInfo {
+    /// Parse a version string
+    pub fn parse(version: &str) -> std::result::Result<Self, String> {
+        let invalid = || format!("invalid version: {version:?}");
+        let trimmed = version.trim();
+        let trimmed = trimmed.strip_prefix('v').unwrap_or(trimmed);
+        let (rest, build) = match trimmed.split_once('+') {
+            Some((rest, build)) => (rest, Some(build.to_string())),
+            None => (trimmed, None),
+        };
+        let (core, pre_release) = match rest.split_once('-') {
+            Some((core, pre)) if !pre.is_empty() => (core, Some(pre.to_string())),
+            Some(_) => return Err(invalid()),
+            None => (rest, None),
+        };
+
+        let mut parts = core.split('.').map(str::parse::<u64>);
+        let mut next = || parts.next().unwrap_or(Ok(0)).map_err(|_| invalid());
+        let (major, minor, patch) = (next()?, next()?, next()?);
+        if core.split('.').count() > 3 {
+            return Err(invalid());
+        }
+
+        Ok(Self {
+            version: version.to_string(),
+            major,
+            minor,
+            patch,
+            pre_release,
+            build,
+            commit: None,
+        })
+    }
+
+    /// Whether this version is at least `major.minor.patch`
+    ///
+    /// Pre-releases of that version do not count.
+    pub fn at_least(&self, major: u64, minor: u64, patch: u64) -> bool {
+        (self.major, self.minor, self.patch) >= (major, minor, patch)
+            && ((self.major, self.minor, self.patch) != (major, minor, patch)
+                || self.pre_release.is_none())
+    }
+}
+
+impl std::str::FromStr for VersionInfo {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+impl std::fmt::Display for VersionInfo {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.version)
+    }
+}
+
+impl PartialEq for VersionInfo {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for VersionInfo {}
+
+impl PartialOrd for VersionInfo {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for VersionInfo {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.major, self.minor, self.patch)
+            .cmp(&(other.major, other.minor, other.patch))
+            .then_with(|| match (&self.pre_release, &other.pre_release) {
+                (None, None) => Ordering::Equal,
+                (None, Some(_)) => Ordering::Greater,
+                (Some(_), None) => Ordering::Less,
+                (Some(a), Some(b)) => compare_pre_release(a, b),
+            })
+    }
+}
+
+/// Compare dot-separated pre-release identifiers, numeric ones numerically
+fn compare_pre_release(a: &str, b: &str) -> Ordering {
+    let mut left = a.split('.');
+    let mut right = b.split('.');
+    loop {
+        let ordering = match (left.next(), right.next()) {
+            (None, None) => return Ordering::Equal,
+            (None, Some(_)) => return Ordering::Less,
+            (Some(_), None) => return Ordering::Greater,
+            (Some(x), Some(y)) => match (x.parse::<u64>(), y.parse::<u64>()) {
+                (Ok(x), Ok(y)) => x.cmp(&y),
+                (Ok(_), Err(_)) => Ordering::Less,
+                (Err(_), Ok(_)) => Ordering::Greater,
+                (Err(_), Err(_)) => x.cmp(y),
+            },
+        };
+        if ordering != Ordering::Equal {
+            return ordering;
+        }
+    }
+}
+
+/// Server feature that not every Ollama release supports
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Feature {
+    /// The batch `/api/embed` endpoint, which replaced `/api/embeddings`
+    Embed,
+    /// Tool calling in chat requests
+    Tools,
+    /// JSON schemas in the `format` field
+    StructuredOutputs,
+    /// Separate reasoning output requested with `think`
+    Thinking,
+}
+
+impl Feature {
+    /// First Ollama release that supports the feature, as `(major, minor, patch)`
+    pub fn min_version(self) -> (u64, u64, u64) {
+        match self {
+            Self::Embed | Self::Tools => (0, 3, 0),
+            Self::StructuredOutputs => (0, 5, 0),
+            Self::Thinking => (0, 9, 0),
+        }
+    }
+
+    /// Request field that uses the feature
+    pub fn parameter(self) -> &'static str {
+        match self {
+            Self::Embed => "input",
+            Self::Tools => "tools",
+            Self::StructuredOutputs => "format",
+            Self::Thinking => "think",
+        }
+    }
+}
+
+impl std::fmt::Display for Feature {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Embed => "the /api/embed endpoint",
+            Self::Tools => "tool calling",
+            Self::StructuredOutputs => "structured outputs",
+            Self::Thinking => "thinking",
+        })
+    }
+}
+
+/// Features a server supports, derived from its version
+///
+/// When the version is unknown, for example behind a gateway that hides
+/// `/api/version` or on a development build reporting `0.0.0`, every feature
+/// is assumed to be supported.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ServerCapabilities {
+    /// Server version, if known
+    pub version: Option<VersionInfo>,
+    /// Whether `/api/embed` is available
+    pub embed: bool,
+    /// Whether chat requests may carry tools
+    pub tools: bool,
+    /// Whether `format` may be a JSON schema
+    pub structured_outputs: bool,
+    /// Whether requests may ask for thinking output
+    pub thinking: bool,
+}
+
+impl ServerCapabilities {
+    /// Derive capabilities from a server version
+    pub fn from_version(version: Option<VersionInfo>) -> Self {
+        let supports = |feature: Feature| {
+            let (major, minor, patch) = feature.min_version();
+            version.as_ref().is_none_or(|version| version.at_least(major, minor, patch))
+        };
+        Self {
+            embed: supports(Feature::Embed),
+            tools: supports(Feature::Tools),
+            structured_outputs: supports(Feature::StructuredOutputs),
+            thinking: supports(Feature::Thinking),
+            version,
+        }
+    }
+
+    /// Whether the server supports a feature
+    pub fn supports(&self, feature: Feature) -> bool {
+        match feature {
+            Feature::Embed => self.embed,
+            Feature::Tools => self.tools,
+            Feature::StructuredOutputs => self.structured_outputs,
+            Feature::Thinking => self.thinking,
+        }
+    }
+}
+
+/// Detailed health check result
+///
+/// Returned by [`OllamaClient::health_detailed`](crate::OllamaClient::health_detailed).
+/// Serializing it keeps the error's message in `error_message`; the typed
+/// error is not serialized.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct HealthStatus {
-    /// Service status
-    pub status: String,
-    /// Timestamp of the check
-    pub timestamp: Option<chrono::DateTime<chrono::Utc>>,
-    /// Additional health information
-    pub details: Option<HashMap<String, serde_json::Value>>,
+    /// Whether the server answered its health endpoint successfully
+    pub healthy: bool,
+    /// When the check started
+    pub timestamp: chrono::DateTime<chrono::Utc>,
+    /// Round-trip time of the health request
+    pub latency: Duration,
+    /// Server version, if it could be read
+    pub version: Option<VersionInfo>,
+    /// Number of models loaded in memory, if it could be read
+    pub loaded_models: Option<usize>,
+    /// The first error encountered, if any
+    #[serde(skip)]
+    pub error: Option<Arc<OllamaError>>,
+    /// Message of the first error encountered, if any
+    pub error_message: Option<String>,
+}
+
+impl HealthStatus {
+    /// Status of a check that ran without errors so far
+    pub(crate) fn new(
+        healthy: bool,
+        timestamp: chrono::DateTime<chrono::Utc>,
+        latency: Duration,
+    ) -> Self {
+        Self {
+            healthy,
+            timestamp,
+            latency,
+            version: None,
+            loaded_models: None,
+            error: None,
+            error_message: None,
+        }
+    }
+
+    /// Record the first error of the check, keeping any earlier one
+    pub(crate) fn record_error(&mut self, error: OllamaError) {
+        if self.error.is_none() {
+            self.error_message = Some(error.to_string());
+            self.error = Some(Arc::new(error));
+        }
+    }
 }
 
 /// Generic API response wrapper
@@ -163,4 +437,47 @@ mod tests {
         assert_eq!(deserialized.status, "Processing");
         assert_eq!(deserialized.detail, Some("Working on step 5 of 10".to_string()));
     }
+
+    #[test]
+    fn test_version_parsing() {
+        let version = VersionInfo::parse("v0.5.0-rc1+abc").unwrap();
+        assert_eq!((version.major, version.minor, version.patch), (0, 5, 0));
+        assert_eq!(version.pre_release.as_deref(), Some("rc1"));
+        assert_eq!(version.build.as_deref(), Some("abc"));
+        assert_eq!(version.to_string(), "v0.5.0-rc1+abc");
+
+        assert_eq!(VersionInfo::parse("0.6").unwrap().patch, 0);
+        assert!(VersionInfo::parse("latest").is_err());
+        assert!(VersionInfo::parse("1.2.3.4").is_err());
+
+        let version: VersionInfo = serde_json::from_str(r#"{"version": "0.6.2"}"#).unwrap();
+        assert_eq!(version, "0.6.2".parse().unwrap());
+        assert!(serde_json::from_str::<VersionInfo>(r#"{"version": "dev"}"#).is_err());
+    }
+
+    #[test]
+    fn test_version_ordering() {
+        let versions = ["0.1.32", "0.5.0-rc.1", "0.5.0-rc.10", "0.5.0-rc1", "0.5.0", "0.10.1"]
+            .map(|v| VersionInfo::parse(v).unwrap());
+        assert!(versions.windows(2).all(|pair| pair[0] < pair[1]));
+        assert_eq!(VersionInfo::parse("v0.5.0+x").unwrap(), versions[4]);
+
+        assert!(versions[4].at_least(0, 5, 0));
+        assert!(!versions[3].at_least(0, 5, 0));
+        assert!(versions[5].at_least(0, 9, 99));
+        assert!(!versions[0].at_least(0, 2, 0));
+    }
+
+    #[test]
+    fn test_capabilities_from_version() {
+        let capabilities = ServerCapabilities::from_version(VersionInfo::parse("0.5.4").ok());
+        assert!(capabilities.embed && capabilities.tools && capabilities.structured_outputs);
+        assert!(!capabilities.supports(Feature::Thinking));
+
+        let capabilities = ServerCapabilities::from_version(VersionInfo::parse("0.2.8").ok());
+        assert!(!capabilities.supports(Feature::Embed) && !capabilities.tools);
+
+        let capabilities = ServerCapabilities::from_version(None);
+        assert!(capabilities.thinking && capabilities.embed);
+    }
 }
@@ -0,0 +1,453 @@
+//! Credential providers and secret redaction
+
+use crate::error::{OllamaError, Result};
+use futures_util::future::BoxFuture;
+use reqwest::header::{AUTHORIZATION, HeaderMap, HeaderValue};
+use std::{
+    collections::{BTreeMap, HashMap},
+    env, fmt,
+    future::Future,
+    path::PathBuf,
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicU64, Ordering},
+    },
+    time::SystemTime,
+};
+use url::Url;
+
+/// Placeholder printed in place of secret values
+pub(crate) const REDACTED: &str = "***";
+
+/// Supplies authentication headers for every request attempt
+///
+/// Register a provider with
+/// [`ClientConfigBuilder::credentials`](crate::ClientConfigBuilder::credentials).
+/// The client asks it for headers before each attempt, including retries, so
+/// rotated credentials take effect without rebuilding the client. Provider
+/// headers replace custom headers from the configuration but not headers set
+/// on the request or through [`RequestOptions`](crate::RequestOptions).
+pub trait CredentialProvider: Send + Sync + fmt::Debug {
+    /// Produce the headers to add to the next request attempt
+    fn headers(&self) -> BoxFuture<'_, Result<HeaderMap>>;
+
+    /// Called when the server rejects an attempt with `401 Unauthorized`
+    ///
+    /// Return `true` when fresh credentials are available, and the request is
+    /// then sent once more with them. The default keeps the `401` response.
+    fn refresh(&self) -> BoxFuture<'_, Result<bool>> {
+        Box::pin(async { Ok(false) })
+    }
+}
+
+impl<P: CredentialProvider + ?Sized> CredentialProvider for Arc<P> {
+    fn headers(&self) -> BoxFuture<'_, Result<HeaderMap>> {
+        (**self).headers()
+    }
+
+    fn refresh(&self) -> BoxFuture<'_, Result<bool>> {
+        (**self).refresh()
+    }
+}
+
+/// A fixed bearer token
+#[derive(Clone)]
+pub struct BearerToken {
+    token: String,
+}
+
+impl BearerToken {
+    /// Create a provider that always sends `Authorization: Bearer <token>`
+    pub fn new<S: Into<String>>(token: S) -> Self {
+        Self { token: token.into() }
+    }
+}
+
+impl fmt::Debug for BearerToken {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BearerToken").field("token", &REDACTED).finish()
+    }
+}
+
+impl CredentialProvider for BearerToken {
+    fn headers(&self) -> BoxFuture<'_, Result<HeaderMap>> {
+        Box::pin(async { bearer_headers(&self.token) })
+    }
+}
+
+/// A bearer token read from an environment variable on every request
+///
+/// Reads `OLLAMA_API_KEY` unless another variable is chosen with
+/// [`EnvApiKey::from_var`]. An unset or empty variable fails the request with
+/// [`OllamaError::AuthenticationError`].
+#[derive(Clone)]
+pub struct EnvApiKey {
+    var: String,
+    lookup: Arc<dyn Fn(&str) -> Option<String> + Send + Sync>,
+}
+
+impl EnvApiKey {
+    /// Variable read by [`EnvApiKey::new`] and [`OllamaClient::from_env`](crate::OllamaClient::from_env)
+    pub const DEFAULT_VAR: &'static str = "OLLAMA_API_KEY";
+
+    /// Read the token from `OLLAMA_API_KEY`
+    pub fn new() -> Self {
+        Self::from_var(Self::DEFAULT_VAR)
+    }
+
+    /// Read the token from the named environment variable
+    pub fn from_var<S: Into<String>>(var: S) -> Self {
+        Self::from_lookup(var, |var| env::var(var).ok())
+    }
+
+    /// Read the token named `var` through `lookup` instead of the process environment
+    ///
+    /// Useful in tests, where changing the real environment is unsound while
+    /// other threads run, and for settings loaded from another source.
+    pub fn from_lookup<S, F>(var: S, lookup: F) -> Self
+    where
+        S: Into<String>,
+        F: Fn(&str) -> Option<String> + Send + Sync + 'static,
+    {
+        Self { var: var.into(), lookup: Arc::new(lookup) }
+    }
+}
+
+impl fmt::Debug for EnvApiKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EnvApiKey").field("var", &self.var).finish_non_exhaustive()
+    }
+}
+
+impl Default for EnvApiKey {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CredentialProvider for EnvApiKey {
+    fn headers(&self) -> BoxFuture<'_, Result<HeaderMap>> {
+        Box::pin(async {
+            match (self.lookup)(&self.var) {
+                Some(token) if !token.trim().is_empty() => bearer_headers(&token),
+                _ => {
+                    Err(OllamaError::AuthenticationError(format!("{} is not set", self.var), None))
+                }
+            }
+        })
+    }
+}
+
+/// A bearer token kept in a file and reloaded when the file changes
+///
+/// The file's metadata is checked before every request, and its contents are
+/// only re-read when its modification time or size changes, so tokens rotated
+/// by an external agent, such as a mounted Kubernetes secret, are picked up
+/// without restarting. A `401` response forces a re-read and a single retry if
+/// the token changed. File access goes through Tokio's blocking pool, so a
+/// slow filesystem does not stall the runtime.
+pub struct TokenFile {
+    path: PathBuf,
+    cached: Mutex<Option<CachedToken>>,
+}
+
+struct CachedToken {
+    stamp: (Option<SystemTime>, u64),
+    token: String,
+}
+
+impl TokenFile {
+    /// Read the bearer token from the file at `path`
+    pub fn new<P: Into<PathBuf>>(path: P) -> Self {
+        Self { path: path.into(), cached: Mutex::new(None) }
+    }
+
+    /// Return the current token, re-reading the file if it changed or `force` is set
+    async fn load(&self, force: bool) -> Result<(String, bool)> {
+        let read_error = |e: std::io::Error| {
+            OllamaError::AuthenticationError(
+                format!("Failed to read token file '{}': {e}", self.path.display()),
+                None,
+            )
+        };
+        let metadata = tokio::fs::metadata(&self.path).await.map_err(read_error)?;
+        let stamp = (metadata.modified().ok(), metadata.len());
+
+        {
+            let cached = self.cached.lock().expect("token cache lock poisoned");
+            if let Some(current) = cached.as_ref().filter(|c| !force && c.stamp == stamp) {
+                return Ok((current.token.clone(), false));
+            }
+        }
+
+        let token = tokio::fs::read_to_string(&self.path).await.map_err(read_error)?;
+        let token = token.trim().to_string();
+        if token.is_empty() {
+            return Err(OllamaError::AuthenticationError(
+                format!("Token file '{}' is empty", self.path.display()),
+                None,
+            ));
+        }
+        let mut cached = self.cached.lock().expect("token cache lock poisoned");
+        let changed = cached.as_ref().is_none_or(|c| c.token != token);
+        *cached = Some(CachedToken { stamp, token: token.clone() });
+        Ok((token, changed))
+    }
+}
+
+impl fmt::Debug for TokenFile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TokenFile").field("path", &self.path).finish_non_exhaustive()
+    }
+}
+
+impl CredentialProvider for TokenFile {
+    fn headers(&self) -> BoxFuture<'_, Result<HeaderMap>> {
+        Box::pin(async { bearer_headers(&self.load(false).await?.0) })
+    }
+
+    fn refresh(&self) -> BoxFuture<'_, Result<bool>> {
+        Box::pin(async { Ok(self.load(true).await?.1) })
+    }
+}
+
+type FetchToken = dyn Fn() -> BoxFuture<'static, Result<String>> + Send + Sync;
+
+/// A bearer token obtained from a callback and renewed when the server returns `401`
+///
+/// The callback runs on first use and again whenever a request is rejected as
+/// unauthorized, for example to exchange a refresh token for a new access
+/// token. Refreshes are serialized with requests waiting for a token, so no
+/// request is sent with a token that is being replaced, and `401`s that arrive
+/// while a refresh runs reuse its result instead of each fetching a new token.
+pub struct RefreshingToken {
+    fetch: Box<FetchToken>,
+    token: tokio::sync::Mutex<RefreshState>,
+    /// Number of completed refreshes, read before waiting for the lock
+    generation: AtomicU64,
+}
+
+#[derive(Default)]
+struct RefreshState {
+    token: Option<String>,
+    /// Whether the last refresh produced a different token
+    changed: bool,
+}
+
+impl RefreshingToken {
+    /// Create a provider that obtains tokens from `fetch`
+    pub fn new<F, Fut>(fetch: F) -> Self
+    where
+        F: Fn() -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<String>> + Send + 'static,
+    {
+        Self {
+            fetch: Box::new(move || Box::pin(fetch())),
+            token: tokio::sync::Mutex::default(),
+            generation: AtomicU64::new(0),
+        }
+    }
+}
+
+impl fmt::Debug for RefreshingToken {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RefreshingToken").field("token", &REDACTED).finish_non_exhaustive()
+    }
+}
+
+impl CredentialProvider for RefreshingToken {
+    fn headers(&self) -> BoxFuture<'_, Result<HeaderMap>> {
+        Box::pin(async {
+            let mut state = self.token.lock().await;
+            if state.token.is_none() {
+                state.token = Some((self.fetch)().await?);
+            }
+            bearer_headers(state.token.as_deref().unwrap_or_default())
+        })
+    }
+
+    fn refresh(&self) -> BoxFuture<'_, Result<bool>> {
+        Box::pin(async {
+            let seen = self.generation.load(Ordering::Acquire);
+            let mut state = self.token.lock().await;
+            // Another request refreshed while this one waited for the lock
+            if self.generation.load(Ordering::Acquire) != seen {
+                return Ok(state.changed);
+            }
+
+            let fresh = (self.fetch)().await?;
+            state.changed = state.token.as_deref() != Some(fresh.as_str());
+            state.token = Some(fresh);
+            self.generation.fetch_add(1, Ordering::Release);
+            Ok(state.changed)
+        })
+    }
+}
+
+/// Build an `Authorization: Bearer` header marked as sensitive
+fn bearer_headers(token: &str) -> Result<HeaderMap> {
+    let mut value = HeaderValue::from_str(&format!("Bearer {}", token.trim())).map_err(|_| {
+        OllamaError::AuthenticationError(
+            "Token contains invalid header characters".to_string(),
+            None,
+        )
+    })?;
+    value.set_sensitive(true);
+
+    let mut headers = HeaderMap::new();
+    headers.insert(AUTHORIZATION, value);
+    Ok(headers)
+}
+
+/// Whether a header or query parameter name usually carries a secret
+pub(crate) fn is_sensitive_name(name: &str) -> bool {
+    let name = name.to_ascii_lowercase();
+    matches!(name.as_str(), "authorization" | "proxy-authorization" | "cookie" | "set-cookie")
+        || ["key", "token", "secret", "password", "signature", "auth"]
+            .iter()
+            .any(|part| name.contains(part))
+}
+
+/// Custom headers with secret values replaced, for `Debug` output
+pub(crate) fn redact_headers(headers: &HashMap<String, String>) -> BTreeMap<&str, &str> {
+    headers
+        .iter()
+        .map(|(name, value)| {
+            let value = if is_sensitive_name(name) { REDACTED } else { value.as_str() };
+            (name.as_str(), value)
+        })
+        .collect()
+}
+
+/// A URL with its password and secret-looking query values replaced
+pub(crate) fn redact_url(url: &Url) -> String {
+    let mut url = url.clone();
+    if url.password().is_some() {
+        let _ = url.set_password(Some(REDACTED));
+    }
+    if url.query().is_some_and(|query| !query.is_empty()) {
+        let pairs: Vec<(String, String)> = url
+            .query_pairs()
+            .map(|(name, value)| {
+                let value = if is_sensitive_name(&name) { REDACTED.into() } else { value };
+                (name.into_owned(), value.into_owned())
+            })
+            .collect();
+        url.query_pairs_mut().clear().extend_pairs(pairs);
+    }
+    url.to_string()
+}
+
+/// Like [`redact_url`] for a URL that may not parse
+pub(crate) fn redact_url_str(raw: &str) -> String {
+    Url::parse(raw).map_or_else(|_| REDACTED.to_string(), |url| redact_url(&url))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{sync::atomic::AtomicUsize, time::Duration};
+
+    fn authorization(headers: &HeaderMap) -> &str {
+        headers[AUTHORIZATION].to_str().unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_bearer_token() {
+        let provider = BearerToken::new("s3cret");
+        let headers = provider.headers().await.unwrap();
+
+        assert_eq!(authorization(&headers), "Bearer s3cret");
+        assert!(headers[AUTHORIZATION].is_sensitive());
+        assert!(!provider.refresh().await.unwrap());
+        assert!(!format!("{provider:?}").contains("s3cret"));
+    }
+
+    #[tokio::test]
+    async fn test_env_api_key() {
+        let value = Arc::new(Mutex::new(None));
+        let provider = EnvApiKey::from_lookup(EnvApiKey::DEFAULT_VAR, {
+            let value = value.clone();
+            move |var: &str| {
+                assert_eq!(var, "OLLAMA_API_KEY");
+                value.lock().unwrap().clone()
+            }
+        });
+        assert!(matches!(provider.headers().await, Err(OllamaError::AuthenticationError(..))));
+
+        *value.lock().unwrap() = Some(" ".to_string());
+        assert!(matches!(provider.headers().await, Err(OllamaError::AuthenticationError(..))));
+
+        *value.lock().unwrap() = Some("from-env".to_string());
+        assert_eq!(authorization(&provider.headers().await.unwrap()), "Bearer from-env");
+        assert!(!format!("{provider:?}").contains("from-env"));
+    }
+
+    #[tokio::test]
+    async fn test_token_file_reloads_on_change() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("token");
+        std::fs::write(&path, "first\n").unwrap();
+
+        let provider = TokenFile::new(&path);
+        assert_eq!(authorization(&provider.headers().await.unwrap()), "Bearer first");
+
+        std::fs::write(&path, "second-token\n").unwrap();
+        assert_eq!(authorization(&provider.headers().await.unwrap()), "Bearer second-token");
+        assert!(!provider.refresh().await.unwrap());
+
+        std::fs::write(&path, "").unwrap();
+        assert!(matches!(provider.headers().await, Err(OllamaError::AuthenticationError(..))));
+    }
+
+    #[tokio::test]
+    async fn test_refreshing_token() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let counter = calls.clone();
+        let provider = RefreshingToken::new(move || {
+            let n = counter.fetch_add(1, Ordering::SeqCst);
+            async move { Ok(format!("token-{n}")) }
+        });
+
+        assert_eq!(authorization(&provider.headers().await.unwrap()), "Bearer token-0");
+        assert_eq!(authorization(&provider.headers().await.unwrap()), "Bearer token-0");
+        assert!(provider.refresh().await.unwrap());
+        assert_eq!(authorization(&provider.headers().await.unwrap()), "Bearer token-1");
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+        assert!(!format!("{provider:?}").contains("token-"));
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_refreshes_fetch_once() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let counter = calls.clone();
+        let provider = RefreshingToken::new(move || {
+            let n = counter.fetch_add(1, Ordering::SeqCst);
+            async move {
+                tokio::time::sleep(Duration::from_millis(20)).await;
+                Ok(format!("token-{n}"))
+            }
+        });
+        provider.headers().await.unwrap();
+
+        let refreshed = futures_util::future::join_all((0..4).map(|_| provider.refresh())).await;
+
+        assert!(refreshed.into_iter().all(|changed| changed.unwrap()));
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+        assert_eq!(authorization(&provider.headers().await.unwrap()), "Bearer token-1");
+
+        // A later 401 still refreshes
+        assert!(provider.refresh().await.unwrap());
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[test]
+    fn test_redact_url() {
+        let url = Url::parse("https://user:pw@gw.example.com/ollama?api_key=abc&tenant=a").unwrap();
+        let redacted = redact_url(&url);
+
+        assert!(!redacted.contains("pw@") && !redacted.contains("abc"));
+        assert!(redacted.contains("tenant=a") && redacted.contains("user:"));
+        assert_eq!(redact_url_str("not a url"), REDACTED);
+    }
+}
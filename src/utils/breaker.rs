@@ -0,0 +1,270 @@
+//! Per-endpoint circuit breaker
+
+use crate::error::{OllamaError, Result};
+use std::{collections::BTreeMap, fmt, sync::Mutex, time::Duration};
+use tokio::time::Instant;
+use url::Url;
+
+/// When a circuit opens and how long it stays open
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CircuitBreakerConfig {
+    /// Consecutive retryable failures that open the circuit
+    pub failure_threshold: u32,
+    /// Time the circuit stays open before a health probe is attempted
+    pub open_duration: Duration,
+}
+
+impl Default for CircuitBreakerConfig {
+    fn default() -> Self {
+        Self { failure_threshold: 5, open_duration: Duration::from_secs(30) }
+    }
+}
+
+/// State of one endpoint's circuit
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CircuitState {
+    /// Requests flow normally
+    Closed,
+    /// Requests fail fast with [`OllamaError::CircuitOpen`]
+    Open,
+    /// A health probe and a single trial request decide whether to close the circuit
+    HalfOpen,
+}
+
+impl fmt::Display for CircuitState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Closed => "closed",
+            Self::Open => "open",
+            Self::HalfOpen => "half-open",
+        })
+    }
+}
+
+/// Snapshot of one endpoint's circuit, for monitoring
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CircuitStatus {
+    /// Endpoint path, such as `/api/generate`
+    pub endpoint: String,
+    /// Current state
+    pub state: CircuitState,
+    /// Retryable failures since the last success
+    pub consecutive_failures: u32,
+    /// Time until an open circuit allows a probe
+    pub retry_in: Option<Duration>,
+}
+
+/// Whether a request may be sent
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Admission {
+    /// The circuit is closed
+    Normal,
+    /// The circuit just became half-open; probe health before sending
+    Trial,
+}
+
+/// Circuits for every endpoint of one host
+#[derive(Debug)]
+pub(crate) struct CircuitBreaker {
+    config: CircuitBreakerConfig,
+    circuits: Mutex<BTreeMap<String, Circuit>>,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Circuit {
+    failures: u32,
+    state: State,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum State {
+    Closed,
+    Open { until: Instant },
+    HalfOpen { since: Instant },
+}
+
+impl CircuitBreaker {
+    pub(crate) fn new(config: CircuitBreakerConfig) -> Self {
+        Self { config, circuits: Mutex::default() }
+    }
+
+    /// Decide whether a request to `endpoint` may be sent
+    ///
+    /// Fails with [`OllamaError::CircuitOpen`] while the circuit is open or
+    /// another request is trialling it. An abandoned trial is given up after
+    /// `open_duration`, so the next request trials instead.
+    pub(crate) fn admit(&self, endpoint: &str) -> Result<Admission> {
+        let now = Instant::now();
+        let mut circuits = self.circuits.lock().expect("circuit lock poisoned");
+        let Some(circuit) = circuits.get_mut(endpoint) else {
+            return Ok(Admission::Normal);
+        };
+
+        match circuit.state {
+            State::Closed => Ok(Admission::Normal),
+            State::Open { until } if now < until => Err(open_error(endpoint, until - now)),
+            State::HalfOpen { since } if now < since + self.config.open_duration => {
+                Err(open_error(endpoint, Duration::ZERO))
+            }
+            State::Open { .. } | State::HalfOpen { .. } => {
+                circuit.state = State::HalfOpen { since: now };
+                Ok(Admission::Trial)
+            }
+        }
+    }
+
+    /// Record a response that shows the endpoint is working
+    pub(crate) fn record_success(&self, endpoint: &str) {
+        let mut circuits = self.circuits.lock().expect("circuit lock poisoned");
+        if let Some(circuit) = circuits.get_mut(endpoint) {
+            if matches!(circuit.state, State::HalfOpen { .. }) {
+                log::info!("Circuit for {endpoint} closed");
+            }
+            *circuit = Circuit { failures: 0, state: State::Closed };
+        }
+    }
+
+    /// Record a retryable failure, opening the circuit at the threshold or after a failed trial
+    pub(crate) fn record_failure(&self, endpoint: &str) {
+        let now = Instant::now();
+        let mut circuits = self.circuits.lock().expect("circuit lock poisoned");
+        let circuit = circuits
+            .entry(endpoint.to_string())
+            .or_insert(Circuit { failures: 0, state: State::Closed });
+
+        circuit.failures = circuit.failures.saturating_add(1);
+        let trip = match circuit.state {
+            State::Closed => circuit.failures >= self.config.failure_threshold.max(1),
+            State::HalfOpen { .. } => true,
+            State::Open { .. } => false,
+        };
+        if trip {
+            log::warn!(
+                "Circuit for {endpoint} opened after {} consecutive failures",
+                circuit.failures
+            );
+            circuit.state = State::Open { until: now + self.config.open_duration };
+        }
+    }
+
+    /// Error returned when a trial's health probe fails
+    pub(crate) fn probe_failed(&self, endpoint: &str) -> OllamaError {
+        self.record_failure(endpoint);
+        open_error(endpoint, self.config.open_duration)
+    }
+
+    /// Snapshot every endpoint that has seen a failure
+    pub(crate) fn status(&self) -> Vec<CircuitStatus> {
+        let now = Instant::now();
+        let circuits = self.circuits.lock().expect("circuit lock poisoned");
+        circuits
+            .iter()
+            .map(|(endpoint, circuit)| {
+                let (state, retry_in) = match circuit.state {
+                    State::Closed => (CircuitState::Closed, None),
+                    State::Open { until } => {
+                        (CircuitState::Open, Some(until.saturating_duration_since(now)))
+                    }
+                    State::HalfOpen { .. } => (CircuitState::HalfOpen, None),
+                };
+                CircuitStatus {
+                    endpoint: endpoint.clone(),
+                    state,
+                    consecutive_failures: circuit.failures,
+                    retry_in,
+                }
+            })
+            .collect()
+    }
+}
+
+fn open_error(endpoint: &str, retry_in: Duration) -> OllamaError {
+    OllamaError::CircuitOpen { endpoint: endpoint.to_string(), retry_in, context: None }
+}
+
+/// Circuit key for a request URL: its path, with blob digests collapsed
+pub(crate) fn endpoint_key(url: &Url) -> String {
+    let path = url.path();
+    match path.rsplit_once('/') {
+        Some((prefix, last)) if last.starts_with("sha256:") => format!("{prefix}/{{digest}}"),
+        _ => path.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn breaker() -> CircuitBreaker {
+        CircuitBreaker::new(CircuitBreakerConfig {
+            failure_threshold: 2,
+            open_duration: Duration::from_secs(10),
+        })
+    }
+
+    fn state(breaker: &CircuitBreaker) -> CircuitState {
+        breaker.status()[0].state
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_opens_after_consecutive_failures() {
+        let breaker = breaker();
+        breaker.record_failure("/api/chat");
+        breaker.record_success("/api/chat");
+        breaker.record_failure("/api/chat");
+        assert_eq!(breaker.admit("/api/chat").unwrap(), Admission::Normal);
+
+        breaker.record_failure("/api/chat");
+        assert_eq!(state(&breaker), CircuitState::Open);
+        assert!(matches!(
+            breaker.admit("/api/chat"),
+            Err(OllamaError::CircuitOpen { retry_in, .. }) if retry_in == Duration::from_secs(10)
+        ));
+        // Other endpoints are unaffected
+        assert_eq!(breaker.admit("/api/embed").unwrap(), Admission::Normal);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_half_open_allows_one_trial() {
+        let breaker = breaker();
+        breaker.record_failure("/api/chat");
+        breaker.record_failure("/api/chat");
+
+        tokio::time::advance(Duration::from_secs(10)).await;
+        assert_eq!(breaker.admit("/api/chat").unwrap(), Admission::Trial);
+        assert_eq!(state(&breaker), CircuitState::HalfOpen);
+        assert!(breaker.admit("/api/chat").is_err());
+
+        breaker.record_success("/api/chat");
+        assert_eq!(state(&breaker), CircuitState::Closed);
+        assert_eq!(breaker.status()[0].consecutive_failures, 0);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_failed_trial_reopens() {
+        let breaker = breaker();
+        breaker.record_failure("/api/chat");
+        breaker.record_failure("/api/chat");
+        tokio::time::advance(Duration::from_secs(10)).await;
+        assert_eq!(breaker.admit("/api/chat").unwrap(), Admission::Trial);
+
+        assert!(matches!(breaker.probe_failed("/api/chat"), OllamaError::CircuitOpen { .. }));
+        assert_eq!(state(&breaker), CircuitState::Open);
+        assert_eq!(breaker.status()[0].retry_in, Some(Duration::from_secs(10)));
+
+        // An abandoned trial is retried after another open period
+        tokio::time::advance(Duration::from_secs(10)).await;
+        assert_eq!(breaker.admit("/api/chat").unwrap(), Admission::Trial);
+        tokio::time::advance(Duration::from_secs(10)).await;
+        assert_eq!(breaker.admit("/api/chat").unwrap(), Admission::Trial);
+    }
+
+    #[test]
+    fn test_endpoint_key() {
+        let url = Url::parse("http://localhost/ollama/api/blobs/sha256:abc").unwrap();
+        assert_eq!(endpoint_key(&url), "/ollama/api/blobs/{digest}");
+
+        let url = Url::parse("http://localhost/api/generate?x=1").unwrap();
+        assert_eq!(endpoint_key(&url), "/api/generate");
+    }
+}
@@ -0,0 +1,126 @@
+//! Cooperative cancellation helpers
+
+use crate::error::{OllamaError, Result};
+use futures_util::{Stream, StreamExt, stream};
+use std::{future::Future, pin::Pin};
+pub use tokio_util::sync::CancellationToken;
+
+/// Drive a future to completion unless the token is cancelled first
+///
+/// The future is dropped on cancellation, which closes any HTTP connection it owns.
+pub async fn run_until_cancelled<F, T>(token: Option<&CancellationToken>, future: F) -> Result<T>
+where
+    F: Future<Output = Result<T>>,
+{
+    let Some(token) = token else {
+        return future.await;
+    };
+
+    if token.is_cancelled() {
+        return Err(OllamaError::Cancelled(None));
+    }
+
+    tokio::select! {
+        biased;
+        () = token.cancelled() => Err(OllamaError::Cancelled(None)),
+        result = future => result,
+    }
+}
+
+struct CancelState<S> {
+    inner: Pin<Box<S>>,
+    token: CancellationToken,
+    done: bool,
+}
+
+/// End a stream with a final [`OllamaError::Cancelled`] item once the token fires
+///
+/// The inner stream is dropped as soon as cancellation is observed so that the
+/// underlying connection is closed and the server stops producing output.
+pub fn cancellable_stream<S, T>(
+    inner: S,
+    token: Option<CancellationToken>,
+) -> Pin<Box<dyn Stream<Item = Result<T>> + Send>>
+where
+    S: Stream<Item = Result<T>> + Send + 'static,
+    T: Send + 'static,
+{
+    let Some(token) = token else {
+        return Box::pin(inner);
+    };
+
+    let state = CancelState { inner: Box::pin(inner), token, done: false };
+
+    Box::pin(stream::unfold(Some(state), |state| async move {
+        let mut state = state?;
+        if state.done {
+            return None;
+        }
+
+        tokio::select! {
+            biased;
+            () = state.token.cancelled() => Some((Err(OllamaError::Cancelled(None)), None)),
+            item = state.inner.next() => {
+                state.done = item.is_none();
+                item.map(|item| (item, Some(state)))
+            }
+        }
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn test_run_without_token() {
+        let result = run_until_cancelled(None, async { Ok(1) }).await;
+        assert_eq!(result.unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_run_with_cancelled_token() {
+        let token = CancellationToken::new();
+        token.cancel();
+
+        let result = run_until_cancelled(Some(&token), async { Ok(1) }).await;
+        assert!(matches!(result, Err(OllamaError::Cancelled(_))));
+    }
+
+    #[tokio::test]
+    async fn test_run_cancelled_while_pending() {
+        let token = CancellationToken::new();
+        let trigger = token.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            trigger.cancel();
+        });
+
+        let result: Result<()> = run_until_cancelled(Some(&token), std::future::pending()).await;
+        assert!(matches!(result, Err(OllamaError::Cancelled(_))));
+    }
+
+    #[tokio::test]
+    async fn test_stream_passes_items_until_cancelled() {
+        let token = CancellationToken::new();
+        let inner = stream::iter(vec![Ok(1), Ok(2)]).chain(stream::pending());
+        let mut stream = cancellable_stream(inner, Some(token.clone()));
+
+        assert_eq!(stream.next().await.unwrap().unwrap(), 1);
+        assert_eq!(stream.next().await.unwrap().unwrap(), 2);
+
+        token.cancel();
+        assert!(matches!(stream.next().await, Some(Err(OllamaError::Cancelled(_)))));
+        assert!(stream.next().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_stream_completes_normally() {
+        let token = CancellationToken::new();
+        let items: Vec<_> =
+            cancellable_stream(stream::iter(vec![Ok(1), Ok(2)]), Some(token)).collect().await;
+
+        assert_eq!(items.len(), 2);
+    }
+}
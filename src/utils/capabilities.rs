@@ -0,0 +1,44 @@
+//! Per-client cache of detected server capabilities
+
+use crate::{
+    error::Result,
+    types::{Feature, ServerCapabilities, VersionInfo},
+};
+use std::sync::atomic::{AtomicBool, Ordering};
+use tokio::sync::OnceCell;
+
+/// Server version and probe results shared by every copy of one client
+#[derive(Debug, Default)]
+pub(crate) struct CapabilityCache {
+    version: OnceCell<Option<VersionInfo>>,
+    legacy_embeddings: AtomicBool,
+}
+
+impl CapabilityCache {
+    /// Get the server version, running `detect` the first time
+    ///
+    /// A failed detection is not cached, so the next call tries again.
+    pub(crate) async fn version<F>(&self, detect: F) -> Result<Option<VersionInfo>>
+    where
+        F: Future<Output = Result<Option<VersionInfo>>>,
+    {
+        self.version.get_or_try_init(|| detect).await.cloned()
+    }
+
+    /// Capabilities for `version`, adjusted by what probing has found
+    pub(crate) fn capabilities(&self, version: Option<VersionInfo>) -> ServerCapabilities {
+        let mut capabilities = ServerCapabilities::from_version(version);
+        capabilities.embed &= !self.legacy_embeddings.load(Ordering::Relaxed);
+        capabilities
+    }
+
+    /// Whether a feature is supported, as far as is known without a request
+    pub(crate) fn supports(&self, feature: Feature) -> bool {
+        self.capabilities(self.version.get().cloned().flatten()).supports(feature)
+    }
+
+    /// Record that the server has no `/api/embed` endpoint
+    pub(crate) fn mark_legacy_embeddings(&self) {
+        self.legacy_embeddings.store(true, Ordering::Relaxed);
+    }
+}
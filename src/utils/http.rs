@@ -2,103 +2,699 @@
 
 use crate::{
     config::ClientConfig,
-    error::{OllamaError, Result},
+    error::{ErrorContext, ErrorHook, OllamaError, Result, server_message},
+    models::{
+        model_name::ModelName,
+        options::{RequestOptions, StreamOptions},
+    },
+    streaming::{
+        ndjson::decode_ndjson,
+        timeout::{StreamTimeouts, with_stream_timeouts},
+    },
+    utils::{
+        auth::{is_sensitive_name, redact_url},
+        breaker::{Admission, CircuitBreaker, CircuitStatus, endpoint_key},
+        cancel::{CancellationToken, cancellable_stream, run_until_cancelled},
+        capabilities::CapabilityCache,
+        limit::Limiter,
+        middleware::Next,
+        retry::{RetryPolicy, parse_retry_after},
+        transport::{Transport, default_client},
+    },
 };
-use reqwest::{Client, RequestBuilder, Response};
-use serde::Serialize;
+use futures_util::{Stream, StreamExt};
+use reqwest::{
+    Method, Request, Response, StatusCode,
+    header::{CONTENT_TYPE, HeaderMap, HeaderName, HeaderValue},
+};
+use serde::{Serialize, de::DeserializeOwned};
+use std::{
+    collections::HashSet,
+    pin::Pin,
+    sync::{
+        Arc,
+        atomic::{AtomicU32, Ordering},
+    },
+    time::Duration,
+};
+use tokio::time::Instant;
 
 /// HTTP client wrapper for Ollama API requests
 #[derive(Debug, Clone)]
 pub struct HttpClient {
-    client: Client,
+    transport: Arc<dyn Transport>,
     config: ClientConfig,
+    limiter: Arc<Limiter>,
+    breaker: Option<Arc<CircuitBreaker>>,
+    capabilities: Arc<CapabilityCache>,
+    retry_policy: RetryPolicy,
+    cancellation: Option<CancellationToken>,
+    request_options: RequestOptions,
+    stream_options: StreamOptions,
 }
 
 impl HttpClient {
     /// Create a new HTTP client with the given configuration
     pub fn new(config: ClientConfig) -> Result<Self> {
-        let mut client_builder =
-            Client::builder().timeout(config.timeout).user_agent(&config.user_agent);
+        let client = default_client(&config)?;
+        Ok(Self::with_transport(config, Arc::new(client)))
+    }
 
-        if config.follow_redirects {
-            client_builder = client_builder.redirect(reqwest::redirect::Policy::limited(10));
-        } else {
-            client_builder = client_builder.redirect(reqwest::redirect::Policy::none());
+    /// Create a new HTTP client that sends requests through the given transport
+    ///
+    /// The transport is responsible for connection-level settings such as the
+    /// connect timeout, user agent and redirect policy; `config` still supplies
+    /// the base URL, custom headers, request timeouts and retry behavior.
+    pub fn with_transport(config: ClientConfig, transport: Arc<dyn Transport>) -> Self {
+        let retry_policy = RetryPolicy::from_config(&config);
+        let limiter = Arc::new(Limiter::new(&config.limits));
+        let breaker = config.circuit_breaker.map(|config| Arc::new(CircuitBreaker::new(config)));
+
+        Self {
+            transport,
+            config,
+            limiter,
+            breaker,
+            capabilities: Arc::default(),
+            retry_policy,
+            cancellation: None,
+            request_options: RequestOptions::default(),
+            stream_options: StreamOptions::default(),
         }
+    }
 
-        let client = client_builder
-            .build()
-            .map_err(|e| OllamaError::ConfigError(format!("Failed to create HTTP client: {e}")))?;
+    /// Get the transport requests are sent through
+    pub fn transport(&self) -> &Arc<dyn Transport> {
+        &self.transport
+    }
+
+    /// Get the state of every endpoint circuit that has seen a failure
+    ///
+    /// Empty when no circuit breaker is configured.
+    pub fn circuit_status(&self) -> Vec<CircuitStatus> {
+        self.breaker.as_ref().map(|breaker| breaker.status()).unwrap_or_default()
+    }
+
+    /// Get the cache of detected server capabilities
+    pub(crate) fn capability_cache(&self) -> &CapabilityCache {
+        &self.capabilities
+    }
+
+    /// Get the retry policy applied to requests
+    pub fn retry_policy(&self) -> RetryPolicy {
+        self.retry_policy
+    }
+
+    /// Create a copy of this client that uses a different retry policy
+    pub fn with_retry_policy(&self, retry_policy: RetryPolicy) -> Self {
+        Self { retry_policy, ..self.clone() }
+    }
+
+    /// Get the cancellation token observed by requests, if any
+    pub fn cancellation_token(&self) -> Option<&CancellationToken> {
+        self.cancellation.as_ref()
+    }
+
+    /// Create a copy of this client whose requests and streams stop when the token is cancelled
+    pub fn with_cancellation(&self, token: CancellationToken) -> Self {
+        Self { cancellation: Some(token), ..self.clone() }
+    }
+
+    /// Get the per-request overrides applied on top of the client configuration
+    pub fn request_options(&self) -> &RequestOptions {
+        &self.request_options
+    }
+
+    /// Create a copy of this client that applies per-request overrides
+    ///
+    /// Fields set in `options` replace earlier overrides; extra headers are
+    /// merged. A `timeout` replaces the configured whole-request timeout, and
+    /// `max_retries` replaces the configured retry count.
+    pub fn with_request_options(&self, options: &RequestOptions) -> Self {
+        let mut merged = self.request_options.clone();
+        merged.timeout = options.timeout.or(merged.timeout);
+        merged.debug = options.debug.or(merged.debug);
+        if let Some(headers) = &options.headers {
+            merged.headers.get_or_insert_default().extend(headers.clone());
+        }
+
+        let mut retry_policy = self.retry_policy;
+        if let Some(max_retries) = options.max_retries {
+            merged.max_retries = Some(max_retries);
+            retry_policy.max_retries = max_retries;
+        }
+
+        Self { retry_policy, request_options: merged, ..self.clone() }
+    }
+
+    /// Get the streaming overrides applied on top of the client configuration
+    pub fn stream_options(&self) -> &StreamOptions {
+        &self.stream_options
+    }
+
+    /// Create a copy of this client that applies streaming overrides
+    ///
+    /// `chunk_timeout` replaces the configured idle timeout between stream chunks.
+    pub fn with_stream_options(&self, options: &StreamOptions) -> Self {
+        let mut merged = self.stream_options.clone();
+        merged.buffer_size = options.buffer_size.or(merged.buffer_size);
+        merged.chunk_timeout = options.chunk_timeout.or(merged.chunk_timeout);
+        merged.include_partial = options.include_partial.or(merged.include_partial);
+
+        Self { stream_options: merged, ..self.clone() }
+    }
+
+    /// Decode a JSON response body, honoring cancellation
+    pub async fn json<T: DeserializeOwned>(&self, response: Response) -> Result<T> {
+        let (info, status) = response_info(&response);
+        run_until_cancelled(self.cancellation.as_ref(), async {
+            response.json().await.map_err(|e| OllamaError::InvalidResponse(e.to_string(), None))
+        })
+        .await
+        .map_err(|error| self.report(error, info.as_ref(), status, None))
+    }
 
-        Ok(Self { client, config })
+    /// Read a non-success response into the matching [`OllamaError`]
+    ///
+    /// Decodes Ollama's `{"error": "..."}` body and classifies it with
+    /// [`OllamaError::from_response`]; `model` is the model the request named.
+    pub async fn error_for_response(&self, response: Response, model: Option<&str>) -> OllamaError {
+        let (info, status) = response_info(&response);
+        let body = run_until_cancelled(self.cancellation.as_ref(), async {
+            Ok(response.text().await.unwrap_or_default())
+        })
+        .await;
+
+        match body {
+            Ok(body) => {
+                let error = OllamaError::from_response(status, &body, model);
+                self.report(error, info.as_ref(), status, Some(server_message(status, &body)))
+            }
+            Err(error) => self.report(error, info.as_ref(), status, None),
+        }
+    }
+
+    /// Attach the request behind `response` to an error the caller built from it
+    pub(crate) fn report_response(&self, response: &Response, error: OllamaError) -> OllamaError {
+        let (info, status) = response_info(response);
+        self.report(error, info.as_ref(), status, None)
+    }
+
+    /// Decode a newline-delimited JSON response body into a typed stream
+    ///
+    /// The stream ends with [`OllamaError::Cancelled`] and drops the connection if
+    /// the client's cancellation token fires while it is being consumed. For
+    /// responses to requests sent with [`PostRequestBuilder::streaming`], it also
+    /// ends with [`OllamaError::FirstChunkTimeout`] or
+    /// [`OllamaError::StreamIdleTimeout`] when the server stalls. The idle timer
+    /// restarts whenever the transport delivers bytes, even partway through a record.
+    pub fn ndjson_stream<T>(
+        &self,
+        response: Response,
+    ) -> Pin<Box<dyn Stream<Item = Result<T>> + Send>>
+    where
+        T: DeserializeOwned + Send + 'static,
+    {
+        let timeouts = response.extensions().get::<StreamTimeouts>().copied();
+        let (info, status) = response_info(&response);
+        let chunks = response.bytes_stream().map(|chunk| chunk.map_err(OllamaError::from));
+
+        // Time chunks rather than records, so a large record arriving slowly is not idle
+        let stream = match timeouts {
+            Some(timeouts) => cancellable_stream(
+                decode_ndjson(with_stream_timeouts(chunks, timeouts)),
+                self.cancellation.clone(),
+            ),
+            None => cancellable_stream(decode_ndjson(chunks), self.cancellation.clone()),
+        };
+        match info {
+            Some(info) => {
+                let hook = self.config.on_error.clone();
+                Box::pin(stream.map(move |item| {
+                    item.map_err(|error| report(hook.as_ref(), error, Some(&info), status, None))
+                }))
+            }
+            None => stream,
+        }
     }
 
     /// Make a GET request
     pub async fn get(&self, path: &str) -> Result<Response> {
-        let url = self.config.endpoint_url(path)?;
-        let request = self.client.get(url);
-        self.send_request(request).await
+        let request = self.request(Method::GET, path)?;
+        self.send_request(request, None, false).await
     }
 
     /// Make a POST request
     pub fn post(&self, path: &str) -> PostRequestBuilder<'_> {
-        let url = self.config.endpoint_url(path).expect("Valid URL");
-        PostRequestBuilder { request: self.client.post(url), http_client: self }
+        PostRequestBuilder {
+            request: self.request(Method::POST, path),
+            http_client: self,
+            model: None,
+            streaming: false,
+        }
     }
 
     /// Make a PUT request
     pub fn put(&self, path: &str) -> PutRequestBuilder<'_> {
-        let url = self.config.endpoint_url(path).expect("Valid URL");
-        PutRequestBuilder { request: self.client.put(url), http_client: self }
+        PutRequestBuilder { request: self.request(Method::PUT, path), http_client: self }
     }
 
     /// Make a DELETE request
     pub fn delete(&self, path: &str) -> DeleteRequestBuilder<'_> {
-        let url = self.config.endpoint_url(path).expect("Valid URL");
-        DeleteRequestBuilder { request: self.client.delete(url), http_client: self }
+        DeleteRequestBuilder {
+            request: self.request(Method::DELETE, path),
+            http_client: self,
+            model: None,
+        }
     }
 
     /// Make a HEAD request
     pub async fn head(&self, path: &str) -> Result<Response> {
-        let url = self.config.endpoint_url(path)?;
-        let request = self.client.head(url);
-        self.send_request(request).await
+        let request = self.request(Method::HEAD, path)?;
+        self.send_request(request, None, false).await
     }
 
-    /// Send a request with common headers and error handling
-    async fn send_request(&self, mut request: RequestBuilder) -> Result<Response> {
-        // Add custom headers
-        for (key, value) in &self.config.headers {
-            request = request.header(key, value);
+    fn request(&self, method: Method, path: &str) -> Result<Request> {
+        Ok(Request::new(method, self.config.endpoint_url(path)?))
+    }
+
+    /// Send a request with common headers, retries and error handling
+    ///
+    /// Transport failures and responses whose status maps to a retryable
+    /// [`OllamaError`] are retried according to the client's [`RetryPolicy`],
+    /// honoring `Retry-After` when the server provides it. A `429` response is
+    /// retried only when it carries a `Retry-After` within the policy's maximum
+    /// delay, and is otherwise returned as [`OllamaError::RateLimitExceeded`].
+    /// Retries only happen before a response is returned, so a stream is never
+    /// replayed once the caller has started consuming it. The whole exchange,
+    /// including backoff sleeps and waits for the client's
+    /// [`RateLimits`](crate::utils::limit::RateLimits), stops with
+    /// [`OllamaError::Cancelled`] when the client's cancellation token fires.
+    ///
+    /// Regular requests are bounded by the whole-request timeout. Streaming
+    /// requests are not, since a long generation is legitimate; instead the
+    /// response must start, and its first chunk arrive, within the first-chunk
+    /// timeout, and later chunks are bounded by the idle timeout.
+    ///
+    /// Errors, including ones later read from the response, carry an
+    /// [`ErrorContext`] describing the request, and are logged and passed to
+    /// the configured error hook.
+    async fn send_request(
+        &self,
+        mut request: Request,
+        model: Option<String>,
+        streaming: bool,
+    ) -> Result<Response> {
+        let mut info = RequestInfo {
+            method: request.method().clone(),
+            endpoint: request.url().path().to_string(),
+            model,
+            attempts: 0,
+            started: Instant::now(),
+            request_id: None,
+        };
+        let attempts = AtomicU32::new(0);
+
+        let result = match self.apply_headers(&mut request) {
+            Ok(explicit) => {
+                info.request_id = request_id(request.headers());
+                let exchange =
+                    Exchange { explicit, model: info.model.as_deref(), attempts: &attempts };
+                self.exchange(request, streaming, exchange).await
+            }
+            Err(error) => Err(error),
+        };
+        info.attempts = attempts.load(Ordering::Relaxed);
+
+        match result {
+            Ok(mut response) => {
+                info.request_id = request_id(response.headers()).or(info.request_id);
+                response.extensions_mut().insert(info);
+                Ok(response)
+            }
+            Err(error) => {
+                let status = matches!(error, OllamaError::RateLimitExceeded { .. }).then_some(429);
+                Err(self.report(error, Some(&info), status, None))
+            }
         }
+    }
 
-        // Add content type for JSON requests
-        request = request.header("Content-Type", "application/json");
+    async fn exchange(
+        &self,
+        mut request: Request,
+        streaming: bool,
+        exchange: Exchange<'_>,
+    ) -> Result<Response> {
+        let timeout = self.request_options.timeout.map(Duration::from_secs);
+        if !streaming {
+            *request.timeout_mut() = Some(timeout.unwrap_or(self.config.timeout));
+            return run_until_cancelled(
+                self.cancellation.as_ref(),
+                self.send_with_retries(request, exchange),
+            )
+            .await;
+        }
 
-        let response = request.send().await.map_err(|e| {
-            if e.is_timeout() { OllamaError::Timeout } else { OllamaError::NetworkError(e) }
-        })?;
+        // An explicit per-request timeout still bounds the whole stream
+        *request.timeout_mut() = timeout;
+        let first_chunk = self.config.first_chunk_timeout();
+        let first_chunk_deadline = Instant::now() + first_chunk;
+        let endpoint = endpoint_key(request.url());
+        let exchange = async {
+            tokio::time::timeout_at(first_chunk_deadline, self.send_with_retries(request, exchange))
+                .await
+                .map_err(|_| {
+                    if let Some(breaker) = &self.breaker {
+                        breaker.record_failure(&endpoint);
+                    }
+                    OllamaError::FirstChunkTimeout(first_chunk, None)
+                })?
+        };
 
+        let mut response = run_until_cancelled(self.cancellation.as_ref(), exchange).await?;
+        let idle = self
+            .stream_options
+            .chunk_timeout
+            .map_or(self.config.chunk_timeout(), Duration::from_secs);
+        response.extensions_mut().insert(StreamTimeouts {
+            first_chunk_deadline,
+            first_chunk,
+            idle,
+        });
         Ok(response)
     }
+
+    async fn send_with_retries(
+        &self,
+        mut request: Request,
+        exchange: Exchange<'_>,
+    ) -> Result<Response> {
+        let Exchange { explicit, model, attempts } = exchange;
+        let endpoint = endpoint_key(request.url());
+        let mut retry = 0;
+        let mut refreshed = false;
+
+        loop {
+            self.authorize(&mut request, &explicit).await?;
+            attempts.fetch_add(1, Ordering::Relaxed);
+
+            // Bodies that cannot be cloned (e.g. streams) are only sent once
+            let Some(attempt) = request.try_clone() else {
+                return self.attempt(request, model, &endpoint).await.and_then(reject_rate_limited);
+            };
+
+            let retry_after = match self.attempt(attempt, model, &endpoint).await {
+                Ok(response) if response.status() == StatusCode::UNAUTHORIZED && !refreshed => {
+                    // Resend once, without counting a retry, if the provider has new credentials
+                    refreshed = true;
+                    match &self.config.credentials {
+                        Some(credentials) if credentials.refresh().await? => {
+                            log::debug!(
+                                "Refreshed credentials after 401 from {} {}",
+                                request.method(),
+                                redact_url(request.url())
+                            );
+                            continue;
+                        }
+                        _ => return Ok(response),
+                    }
+                }
+                Ok(response) => {
+                    let retry_after = parse_retry_after(response.headers());
+                    let retryable = match response.status() {
+                        StatusCode::TOO_MANY_REQUESTS => retry_after.is_some(),
+                        status => OllamaError::ServerError {
+                            status: status.as_u16(),
+                            message: String::new(),
+                            context: None,
+                        }
+                        .is_retryable(),
+                    };
+                    if retry >= self.retry_policy.max_retries || !retryable {
+                        return reject_rate_limited(response);
+                    }
+                    match self.retry_policy.delay_for(retry, retry_after) {
+                        Some(delay) => delay,
+                        None => return reject_rate_limited(response),
+                    }
+                }
+                Err(error @ OllamaError::CircuitOpen { .. }) => return Err(error),
+                Err(error) if retry < self.retry_policy.max_retries && error.is_retryable() => {
+                    self.retry_policy.backoff(retry)
+                }
+                Err(error) => return Err(error),
+            };
+
+            retry += 1;
+            log::debug!(
+                "Retrying {} {} in {:?} (retry {retry} of {})",
+                request.method(),
+                redact_url(request.url()),
+                retry_after,
+                self.retry_policy.max_retries
+            );
+            tokio::time::sleep(retry_after).await;
+        }
+    }
+
+    /// Add per-request and custom headers, returning the names chosen for this request
+    fn apply_headers(&self, request: &mut Request) -> Result<HashSet<HeaderName>> {
+        // Add per-request and then custom headers without overriding ones set on the request itself
+        for (key, value) in self.request_options.headers.iter().flatten() {
+            set_header_if_absent(request, key, value)?;
+        }
+        // Credentials replace configured headers, but not ones chosen for this request
+        let explicit: HashSet<HeaderName> = request.headers().keys().cloned().collect();
+        for (key, value) in &self.config.headers {
+            set_header_if_absent(request, key, value)?;
+        }
+
+        // Default to JSON content unless the caller chose another type
+        request
+            .headers_mut()
+            .entry(CONTENT_TYPE)
+            .or_insert(HeaderValue::from_static("application/json"));
+
+        Ok(explicit)
+    }
+
+    /// Send one attempt through the endpoint's circuit breaker, if any
+    ///
+    /// An open circuit fails with [`OllamaError::CircuitOpen`] without sending.
+    /// The first request after the open period only goes out if a health probe
+    /// of the server root succeeds, and its outcome decides whether the circuit
+    /// closes.
+    async fn attempt(
+        &self,
+        request: Request,
+        model: Option<&str>,
+        endpoint: &str,
+    ) -> Result<Response> {
+        let Some(breaker) = &self.breaker else {
+            return self.dispatch(request, model).await;
+        };
+        if breaker.admit(endpoint)? == Admission::Trial && !self.probe().await {
+            return Err(breaker.probe_failed(endpoint));
+        }
+
+        let result = self.dispatch(request, model).await;
+        match &result {
+            Ok(response) if response.status().is_server_error() => breaker.record_failure(endpoint),
+            Ok(_) => breaker.record_success(endpoint),
+            Err(error) if error.is_retryable() => breaker.record_failure(endpoint),
+            Err(_) => {}
+        }
+        result
+    }
+
+    /// Attach the request to an error, log it and pass it to the error hook
+    fn report(
+        &self,
+        error: OllamaError,
+        info: Option<&RequestInfo>,
+        status: impl Into<Option<u16>>,
+        message: Option<String>,
+    ) -> OllamaError {
+        report(self.config.on_error.as_ref(), error, info, status, message)
+    }
+
+    /// Check the server root like [`OllamaClient::health`](crate::OllamaClient::health), bypassing circuits and retries
+    async fn probe(&self) -> bool {
+        let probe = async {
+            let mut request = self.request(Method::GET, "")?;
+            *request.timeout_mut() = Some(self.config.timeout);
+            let explicit = self.apply_headers(&mut request)?;
+            self.authorize(&mut request, &explicit).await?;
+            self.dispatch(request, None).await
+        };
+        probe.await.is_ok_and(|response| response.status().is_success())
+    }
+
+    /// Add the credential provider's current headers, keeping explicitly chosen ones
+    async fn authorize(&self, request: &mut Request, explicit: &HashSet<HeaderName>) -> Result<()> {
+        let Some(credentials) = &self.config.credentials else {
+            return Ok(());
+        };
+
+        let headers = credentials.headers().await?;
+        for name in headers.keys().filter(|name| !explicit.contains(*name)) {
+            request.headers_mut().remove(name);
+        }
+        for (name, value) in headers.iter().filter(|(name, _)| !explicit.contains(*name)) {
+            request.headers_mut().append(name, value.clone());
+        }
+        Ok(())
+    }
+
+    /// Run a single attempt through the rate limiter, the middleware chain and the transport
+    async fn dispatch(&self, request: Request, model: Option<&str>) -> Result<Response> {
+        let permits = self.limiter.acquire(model).await;
+        let next = Next::new(&self.config.middleware, self.transport.as_ref());
+        if self.request_options.debug != Some(true) {
+            return next.run(request).await.map(|response| permits.hold_until_body_done(response));
+        }
+
+        let (method, url, started) =
+            (request.method().clone(), redact_url(request.url()), Instant::now());
+        let result = next.run(request).await;
+        match &result {
+            Ok(response) => {
+                log::info!("{method} {url} -> {} in {:?}", response.status(), started.elapsed());
+            }
+            Err(error) => {
+                log::info!("{method} {url} failed after {:?}: {error}", started.elapsed())
+            }
+        }
+        result.map(|response| permits.hold_until_body_done(response))
+    }
+}
+
+/// Headers that carry a request id, in order of preference
+const REQUEST_ID_HEADERS: &[&str] = &["x-request-id", "request-id", "x-correlation-id"];
+
+/// Details of a sent request, kept on its response to report later errors
+#[derive(Debug, Clone)]
+struct RequestInfo {
+    method: Method,
+    endpoint: String,
+    model: Option<String>,
+    attempts: u32,
+    started: Instant,
+    request_id: Option<String>,
+}
+
+/// Per-call state shared by the attempts of one request
+struct Exchange<'a> {
+    /// Header names chosen for this request, which credentials must not replace
+    explicit: HashSet<HeaderName>,
+    /// Model the request targets, for per-model limits
+    model: Option<&'a str>,
+    /// Attempts sent so far, read back for error context
+    attempts: &'a AtomicU32,
+}
+
+fn request_id(headers: &HeaderMap) -> Option<String> {
+    REQUEST_ID_HEADERS.iter().find_map(|name| headers.get(*name)?.to_str().ok()).map(str::to_string)
+}
+
+fn response_info(response: &Response) -> (Option<RequestInfo>, u16) {
+    (response.extensions().get::<RequestInfo>().cloned(), response.status().as_u16())
+}
+
+/// Attach the request to an error, log it and pass it to the error hook
+fn report(
+    hook: Option<&ErrorHook>,
+    error: OllamaError,
+    info: Option<&RequestInfo>,
+    status: impl Into<Option<u16>>,
+    message: Option<String>,
+) -> OllamaError {
+    let Some(info) = info else {
+        return error;
+    };
+    let context = ErrorContext {
+        method: info.method.to_string(),
+        endpoint: info.endpoint.clone(),
+        model: info.model.clone(),
+        attempt: info.attempts,
+        elapsed: info.started.elapsed(),
+        request_id: info.request_id.clone(),
+        status: status.into(),
+        message,
+    };
+    let error = error.attach(context);
+    log::debug!("{error}");
+    if let Some(hook) = hook {
+        hook(&error);
+    }
+    error
+}
+
+/// Turn a `429 Too Many Requests` response into [`OllamaError::RateLimitExceeded`]
+fn reject_rate_limited(response: Response) -> Result<Response> {
+    if response.status() == StatusCode::TOO_MANY_REQUESTS {
+        return Err(OllamaError::RateLimitExceeded {
+            retry_after: parse_retry_after(response.headers()),
+            context: None,
+        });
+    }
+    Ok(response)
+}
+
+fn parse_header_name(name: &str) -> Result<HeaderName> {
+    HeaderName::from_bytes(name.as_bytes())
+        .map_err(|e| OllamaError::ConfigError(format!("Invalid header name '{name}': {e}")))
+}
+
+fn parse_header_value(value: &str) -> Result<HeaderValue> {
+    HeaderValue::from_str(value)
+        .map_err(|e| OllamaError::ConfigError(format!("Invalid header value: {e}")))
+}
+
+fn set_header_if_absent(request: &mut Request, key: &str, value: &str) -> Result<()> {
+    let name = parse_header_name(key)?;
+    if !request.headers().contains_key(&name) {
+        let mut value = parse_header_value(value)?;
+        value.set_sensitive(is_sensitive_name(key));
+        request.headers_mut().insert(name, value);
+    }
+    Ok(())
+}
+
+fn set_json<T: Serialize>(mut request: Request, json: &T) -> Result<Request> {
+    *request.body_mut() = Some(serde_json::to_vec(json)?.into());
+    request.headers_mut().insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+    Ok(request)
+}
+
+fn set_body<T: Into<reqwest::Body>>(mut request: Request, body: T) -> Request {
+    *request.body_mut() = Some(body.into());
+    request
+}
+
+fn set_header(mut request: Request, key: &str, value: &str) -> Result<Request> {
+    request.headers_mut().insert(parse_header_name(key)?, parse_header_value(value)?);
+    Ok(request)
 }
 
 /// Builder for POST requests
 pub struct PostRequestBuilder<'a> {
-    request: RequestBuilder,
+    request: Result<Request>,
     http_client: &'a HttpClient,
+    model: Option<String>,
+    streaming: bool,
 }
 
 impl PostRequestBuilder<'_> {
     /// Set JSON body
     pub fn json<T: Serialize>(mut self, json: &T) -> Self {
-        self.request = self.request.json(json);
+        self.request = self.request.and_then(|request| set_json(request, json));
         self
     }
 
     /// Set raw body
     pub fn body<T: Into<reqwest::Body>>(mut self, body: T) -> Self {
-        self.request = self.request.body(body);
+        self.request = self.request.map(|request| set_body(request, body));
         self
     }
 
@@ -108,32 +704,57 @@ impl PostRequestBuilder<'_> {
         K: AsRef<str>,
         V: AsRef<str>,
     {
-        self.request = self.request.header(key.as_ref(), value.as_ref());
+        self.request =
+            self.request.and_then(|request| set_header(request, key.as_ref(), value.as_ref()));
+        self
+    }
+
+    /// Name the model the request targets, for per-model limits and error reports
+    ///
+    /// Sending fails with [`OllamaError::InvalidModelName`], before anything goes
+    /// out, if the name is not a valid [`ModelName`].
+    pub fn model<S: Into<String>>(mut self, model: S) -> Self {
+        let model = model.into();
+        self.request = self.request.and_then(|request| {
+            ModelName::parse(&model)?;
+            Ok(request)
+        });
+        self.model = Some(model);
+        self
+    }
+
+    /// Mark the request as returning a long-lived stream
+    ///
+    /// Streaming requests use the first-chunk and idle timeouts instead of the
+    /// whole-request timeout; decode the response with [`HttpClient::ndjson_stream`].
+    pub fn streaming(mut self) -> Self {
+        self.streaming = true;
         self
     }
 
     /// Send the request
     pub async fn send(self) -> Result<Response> {
-        self.http_client.send_request(self.request).await
+        self.http_client.send_request(self.request?, self.model, self.streaming).await
     }
 }
 
 /// Builder for PUT requests
 pub struct PutRequestBuilder<'a> {
-    request: RequestBuilder,
+    request: Result<Request>,
     http_client: &'a HttpClient,
 }
 
 /// Builder for DELETE requests
 pub struct DeleteRequestBuilder<'a> {
-    request: RequestBuilder,
+    request: Result<Request>,
     http_client: &'a HttpClient,
+    model: Option<String>,
 }
 
 impl PutRequestBuilder<'_> {
     /// Set raw body
     pub fn body<T: Into<reqwest::Body>>(mut self, body: T) -> Self {
-        self.request = self.request.body(body);
+        self.request = self.request.map(|request| set_body(request, body));
         self
     }
 
@@ -143,20 +764,21 @@ impl PutRequestBuilder<'_> {
         K: AsRef<str>,
         V: AsRef<str>,
     {
-        self.request = self.request.header(key.as_ref(), value.as_ref());
+        self.request =
+            self.request.and_then(|request| set_header(request, key.as_ref(), value.as_ref()));
         self
     }
 
     /// Send the request
     pub async fn send(self) -> Result<Response> {
-        self.http_client.send_request(self.request).await
+        self.http_client.send_request(self.request?, None, false).await
     }
 }
 
 impl DeleteRequestBuilder<'_> {
     /// Set JSON body
     pub fn json<T: Serialize>(mut self, json: &T) -> Self {
-        self.request = self.request.json(json);
+        self.request = self.request.and_then(|request| set_json(request, json));
         self
     }
 
@@ -166,19 +788,39 @@ impl DeleteRequestBuilder<'_> {
         K: AsRef<str>,
         V: AsRef<str>,
     {
-        self.request = self.request.header(key.as_ref(), value.as_ref());
+        self.request =
+            self.request.and_then(|request| set_header(request, key.as_ref(), value.as_ref()));
+        self
+    }
+
+    /// Name the model the request targets, for per-model limits and error reports
+    ///
+    /// Sending fails with [`OllamaError::InvalidModelName`], before anything goes
+    /// out, if the name is not a valid [`ModelName`].
+    pub fn model<S: Into<String>>(mut self, model: S) -> Self {
+        let model = model.into();
+        self.request = self.request.and_then(|request| {
+            ModelName::parse(&model)?;
+            Ok(request)
+        });
+        self.model = Some(model);
         self
     }
 
     /// Send the request
     pub async fn send(self) -> Result<Response> {
-        self.http_client.send_request(self.request).await
+        self.http_client.send_request(self.request?, self.model, false).await
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::time::Duration;
+    use wiremock::{
+        Mock, MockServer, ResponseTemplate,
+        matchers::{header, method, path},
+    };
 
     #[test]
     fn test_http_client_creation() {
@@ -195,4 +837,733 @@ mod tests {
         let client = HttpClient::new(config);
         assert!(client.is_ok());
     }
+
+    fn retrying_client(uri: String, max_retries: u32) -> HttpClient {
+        let config = ClientConfig::builder()
+            .base_url(uri)
+            .max_retries(max_retries)
+            .retry_delay(Duration::from_millis(1))
+            .build()
+            .unwrap();
+        HttpClient::new(config).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_retries_retryable_status_until_success() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/api/tags"))
+            .respond_with(ResponseTemplate::new(503))
+            .up_to_n_times(2)
+            .expect(2)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/tags"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let client = retrying_client(mock_server.uri(), 3);
+        let response = client.get("api/tags").await.unwrap();
+
+        assert_eq!(response.status().as_u16(), 200);
+    }
+
+    #[tokio::test]
+    async fn test_retry_gives_up_after_max_retries() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/api/generate"))
+            .respond_with(ResponseTemplate::new(500))
+            .expect(3)
+            .mount(&mock_server)
+            .await;
+
+        let client = retrying_client(mock_server.uri(), 2);
+        let response = client.post("api/generate").json(&"{}").send().await.unwrap();
+
+        assert_eq!(response.status().as_u16(), 500);
+    }
+
+    #[tokio::test]
+    async fn test_does_not_retry_client_errors() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("DELETE"))
+            .and(path("/api/delete"))
+            .respond_with(ResponseTemplate::new(404))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let client = retrying_client(mock_server.uri(), 3);
+        let response = client.delete("api/delete").send().await.unwrap();
+
+        assert_eq!(response.status().as_u16(), 404);
+    }
+
+    #[tokio::test]
+    async fn test_honors_retry_after() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("HEAD"))
+            .and(path("/api/blobs/sha256:abc"))
+            .respond_with(ResponseTemplate::new(503).insert_header("Retry-After", "1"))
+            .up_to_n_times(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("HEAD"))
+            .and(path("/api/blobs/sha256:abc"))
+            .respond_with(ResponseTemplate::new(200))
+            .mount(&mock_server)
+            .await;
+
+        let client = retrying_client(mock_server.uri(), 1);
+        let started = std::time::Instant::now();
+        let response = client.head("api/blobs/sha256:abc").await.unwrap();
+
+        assert_eq!(response.status().as_u16(), 200);
+        assert!(started.elapsed() >= Duration::from_secs(1));
+    }
+
+    #[tokio::test]
+    async fn test_without_retries_sends_once() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/api/ps"))
+            .respond_with(ResponseTemplate::new(502))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let client = retrying_client(mock_server.uri(), 3).with_retry_policy(RetryPolicy::none());
+        let response = client.get("api/ps").await.unwrap();
+
+        assert_eq!(response.status().as_u16(), 502);
+    }
+
+    #[tokio::test]
+    async fn test_connection_errors_are_surfaced_after_retries() {
+        // Bind and immediately drop a listener so the port is very likely closed
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        drop(listener);
+
+        let client = retrying_client(format!("http://{address}"), 2);
+        let result = client.get("api/tags").await;
+
+        assert!(matches!(result, Err(OllamaError::NetworkError(..))));
+    }
+
+    #[tokio::test]
+    async fn test_request_headers_take_precedence_over_config_headers() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("PUT"))
+            .and(path("/api/blobs/sha256:abc"))
+            .and(header("content-type", "application/octet-stream"))
+            .and(header("x-tenant", "request"))
+            .respond_with(ResponseTemplate::new(201))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let config = ClientConfig::builder()
+            .base_url(mock_server.uri())
+            .header("X-Tenant", "config")
+            .build()
+            .unwrap();
+        let client = HttpClient::new(config).unwrap();
+        let response = client
+            .put("api/blobs/sha256:abc")
+            .header("Content-Type", "application/octet-stream")
+            .header("X-Tenant", "request")
+            .body(vec![1, 2, 3])
+            .send()
+            .await
+            .unwrap();
+
+        assert_eq!(response.status().as_u16(), 201);
+    }
+
+    #[tokio::test]
+    async fn test_invalid_header_is_reported_on_send() {
+        let client = HttpClient::new(ClientConfig::default()).unwrap();
+        let result = client.post("api/generate").header("bad header", "value").send().await;
+
+        assert!(matches!(result, Err(OllamaError::ConfigError(_))));
+    }
+
+    fn headers(pairs: &[(&str, &str)]) -> Option<std::collections::HashMap<String, String>> {
+        Some(pairs.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect())
+    }
+
+    #[tokio::test]
+    async fn test_request_options_timeout_overrides_config() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/api/embed"))
+            .respond_with(ResponseTemplate::new(200).set_delay(Duration::from_secs(3)))
+            .mount(&mock_server)
+            .await;
+
+        let client = retrying_client(mock_server.uri(), 0)
+            .with_request_options(&RequestOptions { timeout: Some(1), ..Default::default() });
+        let started = Instant::now();
+        let result = client.post("api/embed").json(&"{}").send().await;
+
+        assert!(matches!(result, Err(OllamaError::Timeout(_))));
+        assert!(started.elapsed() < Duration::from_secs(3));
+    }
+
+    #[tokio::test]
+    async fn test_request_options_max_retries_overrides_config() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/api/tags"))
+            .respond_with(ResponseTemplate::new(503))
+            .expect(2)
+            .mount(&mock_server)
+            .await;
+
+        let client = retrying_client(mock_server.uri(), 5)
+            .with_request_options(&RequestOptions { max_retries: Some(1), ..Default::default() });
+        let response = client.get("api/tags").await.unwrap();
+
+        assert_eq!(response.status().as_u16(), 503);
+        assert_eq!(client.retry_policy().max_retries, 1);
+    }
+
+    #[tokio::test]
+    async fn test_request_options_headers_merge_and_override_config() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/api/ps"))
+            .and(header("x-tenant", "per-call"))
+            .and(header("x-trace", "1"))
+            .and(header("x-static", "config"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let config = ClientConfig::builder()
+            .base_url(mock_server.uri())
+            .header("X-Tenant", "config")
+            .header("X-Static", "config")
+            .build()
+            .unwrap();
+        let client = HttpClient::new(config)
+            .unwrap()
+            .with_request_options(&RequestOptions {
+                headers: headers(&[("x-tenant", "per-call")]),
+                ..Default::default()
+            })
+            .with_request_options(&RequestOptions {
+                headers: headers(&[("X-Trace", "1")]),
+                debug: Some(true),
+                ..Default::default()
+            });
+        let response = client.get("api/ps").await.unwrap();
+
+        assert_eq!(response.status().as_u16(), 200);
+        assert_eq!(client.request_options().headers.as_ref().map(|h| h.len()), Some(2));
+        assert_eq!(client.request_options().debug, Some(true));
+    }
+
+    #[tokio::test]
+    async fn test_credentials_replace_config_header_but_not_request_header() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/api/tags"))
+            .and(header("authorization", "Bearer provider"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("DELETE"))
+            .and(path("/api/delete"))
+            .and(header("authorization", "Bearer explicit"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let config = ClientConfig::builder()
+            .base_url(mock_server.uri())
+            .header("Authorization", "Bearer static")
+            .bearer_token("provider")
+            .build()
+            .unwrap();
+        let client = HttpClient::new(config).unwrap();
+
+        assert_eq!(client.get("api/tags").await.unwrap().status().as_u16(), 200);
+        let response =
+            client.delete("api/delete").header("Authorization", "Bearer explicit").send().await;
+        assert_eq!(response.unwrap().status().as_u16(), 200);
+    }
+
+    #[tokio::test]
+    async fn test_refreshes_credentials_once_on_unauthorized() {
+        use crate::utils::auth::RefreshingToken;
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/api/ps"))
+            .and(header("authorization", "Bearer token-1"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/ps"))
+            .respond_with(ResponseTemplate::new(401))
+            .expect(3)
+            .mount(&mock_server)
+            .await;
+
+        let fetches = Arc::new(AtomicUsize::new(0));
+        let counter = fetches.clone();
+        let config = ClientConfig::builder()
+            .base_url(mock_server.uri())
+            .max_retries(0)
+            .credentials(RefreshingToken::new(move || {
+                let n = counter.fetch_add(1, Ordering::SeqCst);
+                async move { Ok(format!("token-{n}")) }
+            }))
+            .build()
+            .unwrap();
+        let client = HttpClient::new(config).unwrap();
+
+        // The stale token is rejected, refreshed and accepted on the resend
+        assert_eq!(client.get("api/ps").await.unwrap().status().as_u16(), 200);
+        assert_eq!(fetches.load(Ordering::SeqCst), 2);
+
+        // A request that is still rejected after a refresh is resent only once
+        let client = client.with_request_options(&RequestOptions {
+            headers: headers(&[("authorization", "Bearer revoked")]),
+            ..Default::default()
+        });
+        assert_eq!(client.get("api/ps").await.unwrap().status().as_u16(), 401);
+        assert_eq!(fetches.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_static_credentials_return_unauthorized() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/api/tags"))
+            .respond_with(ResponseTemplate::new(401))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let config =
+            ClientConfig::builder().base_url(mock_server.uri()).bearer_token("wrong").build();
+        let client = HttpClient::new(config.unwrap()).unwrap();
+
+        assert_eq!(client.get("api/tags").await.unwrap().status().as_u16(), 401);
+    }
+
+    #[tokio::test]
+    async fn test_credential_errors_fail_the_request() {
+        let config = ClientConfig::builder()
+            .credentials(crate::utils::auth::EnvApiKey::from_var("OLLAMA_SDK_TEST_UNSET_KEY"))
+            .build()
+            .unwrap();
+        let client = HttpClient::new(config).unwrap();
+
+        let result = client.get("api/tags").await;
+        assert!(matches!(result, Err(OllamaError::AuthenticationError(..))));
+    }
+
+    #[tokio::test]
+    async fn test_too_many_requests_maps_to_rate_limit_error() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/api/embed"))
+            .respond_with(ResponseTemplate::new(429).insert_header("Retry-After", "0"))
+            .up_to_n_times(1)
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/api/embed"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/api/chat"))
+            .respond_with(ResponseTemplate::new(429).insert_header("Retry-After", "120"))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/api/generate"))
+            .respond_with(ResponseTemplate::new(429))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let client = retrying_client(mock_server.uri(), 3);
+
+        // A short Retry-After is honored by retrying
+        let response = client.post("api/embed").json(&"{}").send().await.unwrap();
+        assert_eq!(response.status().as_u16(), 200);
+
+        // A long or missing Retry-After is surfaced without retrying
+        let error = client.post("api/chat").json(&"{}").streaming().send().await.unwrap_err();
+        assert!(matches!(error, OllamaError::RateLimitExceeded { .. }));
+        assert_eq!(error.retry_after(), Some(Duration::from_mins(2)));
+
+        let error = client.post("api/generate").json(&"{}").send().await.unwrap_err();
+        assert!(matches!(error, OllamaError::RateLimitExceeded { retry_after: None, .. }));
+        assert_eq!(error.status_code(), Some(429));
+    }
+
+    #[tokio::test]
+    async fn test_max_in_flight_limits_concurrency() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/api/ps"))
+            .respond_with(ResponseTemplate::new(200).set_delay(Duration::from_millis(200)))
+            .expect(4)
+            .mount(&mock_server)
+            .await;
+
+        let config =
+            ClientConfig::builder().base_url(mock_server.uri()).max_in_flight(2).build().unwrap();
+        let client = HttpClient::new(config).unwrap();
+
+        let started = Instant::now();
+        let requests = (0..4).map(|_| async {
+            let response = client.get("api/ps").await.unwrap();
+            response.bytes().await.unwrap();
+        });
+        futures_util::future::join_all(requests).await;
+
+        assert!(started.elapsed() >= Duration::from_millis(400));
+    }
+
+    #[tokio::test]
+    async fn test_per_model_permit_is_held_until_response_is_dropped() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/api/generate"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("{\"done\":true}\n"))
+            .mount(&mock_server)
+            .await;
+
+        let config = ClientConfig::builder()
+            .base_url(mock_server.uri())
+            .max_in_flight_per_model(1)
+            .build()
+            .unwrap();
+        let client = HttpClient::new(config).unwrap();
+        let send = |model: &'static str| {
+            client
+                .post("api/generate")
+                .json(&serde_json::json!({"model": model}))
+                .model(model)
+                .send()
+        };
+
+        let first = send("a").await.unwrap();
+        assert!(tokio::time::timeout(Duration::from_millis(100), send("a")).await.is_err());
+        let other_model = send("b").await.unwrap();
+
+        drop(first);
+        let second = tokio::time::timeout(Duration::from_secs(5), send("a")).await.unwrap();
+        assert_eq!(second.unwrap().status().as_u16(), 200);
+        assert_eq!(other_model.text().await.unwrap(), "{\"done\":true}\n");
+    }
+
+    fn breaker_client(uri: String) -> HttpClient {
+        let config = ClientConfig::builder()
+            .base_url(uri)
+            .max_retries(0)
+            .circuit_breaker(2, Duration::from_millis(300))
+            .build()
+            .unwrap();
+        HttpClient::new(config).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_circuit_opens_and_fails_fast() {
+        use crate::utils::breaker::CircuitState;
+
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/api/generate"))
+            .respond_with(ResponseTemplate::new(503))
+            .expect(2)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/tags"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let client = breaker_client(mock_server.uri());
+        for _ in 0..2 {
+            let response = client.post("api/generate").json(&"{}").send().await.unwrap();
+            assert_eq!(response.status().as_u16(), 503);
+        }
+
+        let error = client.post("api/generate").json(&"{}").streaming().send().await.unwrap_err();
+        assert!(
+            matches!(error, OllamaError::CircuitOpen { endpoint, .. } if endpoint == "/api/generate")
+        );
+
+        let status = client.circuit_status();
+        assert_eq!(status.len(), 1);
+        assert_eq!(status[0].state, CircuitState::Open);
+        assert_eq!(status[0].consecutive_failures, 2);
+
+        // Other endpoints keep working
+        client.get("api/tags").await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_circuit_closes_after_successful_probe_and_trial() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/api/ps"))
+            .respond_with(ResponseTemplate::new(500))
+            .up_to_n_times(2)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/ps"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/"))
+            .respond_with(ResponseTemplate::new(500))
+            .up_to_n_times(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let client = breaker_client(mock_server.uri());
+        client.get("api/ps").await.unwrap();
+        client.get("api/ps").await.unwrap();
+
+        // A failed probe keeps the circuit open without sending the request
+        tokio::time::sleep(Duration::from_millis(350)).await;
+        assert!(matches!(client.get("api/ps").await, Err(OllamaError::CircuitOpen { .. })));
+        assert!(matches!(client.get("api/ps").await, Err(OllamaError::CircuitOpen { .. })));
+
+        tokio::time::sleep(Duration::from_millis(350)).await;
+        assert_eq!(client.get("api/ps").await.unwrap().status().as_u16(), 200);
+        assert_eq!(client.circuit_status()[0].consecutive_failures, 0);
+    }
+
+    #[tokio::test]
+    async fn test_error_hook_sees_request_context() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/api/generate"))
+            .respond_with(
+                ResponseTemplate::new(500)
+                    .insert_header("x-request-id", "abc")
+                    .set_body_json(serde_json::json!({"error": "boom"})),
+            )
+            .expect(2)
+            .mount(&mock_server)
+            .await;
+
+        let seen = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let config = ClientConfig::builder()
+            .base_url(mock_server.uri())
+            .max_retries(1)
+            .retry_delay(Duration::from_millis(1))
+            .on_error({
+                let seen = seen.clone();
+                move |error: &OllamaError| {
+                    seen.lock().unwrap().push((error.context().cloned(), error.to_string()))
+                }
+            })
+            .build()
+            .unwrap();
+        let client = HttpClient::new(config).unwrap();
+        let response = client
+            .post("api/generate")
+            .json(&serde_json::json!({"model": "m"}))
+            .model("m")
+            .send()
+            .await
+            .unwrap();
+        let error = client.error_for_response(response, Some("m")).await;
+
+        assert!(matches!(error, OllamaError::ServerError { status: 500, .. }));
+        let seen = seen.lock().unwrap();
+        let (context, message) = &seen[0];
+        let context = context.as_ref().unwrap();
+        assert_eq!(seen.len(), 1);
+        assert_eq!(context.method, "POST");
+        assert_eq!(context.endpoint, "/api/generate");
+        assert_eq!(context.model.as_deref(), Some("m"));
+        assert_eq!(context.attempt, 2);
+        assert_eq!(context.request_id.as_deref(), Some("abc"));
+        assert_eq!(context.status, Some(500));
+        assert_eq!(context.message.as_deref(), Some("boom"));
+        assert!(message.contains("(POST /api/generate, model m, attempt 2, after"), "{message}");
+    }
+
+    #[tokio::test]
+    async fn test_error_hook_keeps_message_of_model_errors() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/api/show"))
+            .respond_with(ResponseTemplate::new(404).set_body_json(
+                serde_json::json!({"error": "model 'm' not found, try pulling it first"}),
+            ))
+            .mount(&mock_server)
+            .await;
+
+        let seen = Arc::new(std::sync::Mutex::new(None));
+        let config = ClientConfig::builder()
+            .base_url(mock_server.uri())
+            .on_error({
+                let seen = seen.clone();
+                move |error: &OllamaError| *seen.lock().unwrap() = error.context().cloned()
+            })
+            .build()
+            .unwrap();
+        let client = HttpClient::new(config).unwrap();
+        let response =
+            client.post("api/show").json(&serde_json::json!({"model": "m"})).model("m").send();
+        let error = client.error_for_response(response.await.unwrap(), Some("m")).await;
+
+        assert!(matches!(error, OllamaError::ModelNotFound(ref model, _) if model == "m"));
+        let context = seen.lock().unwrap().clone().unwrap();
+        assert_eq!(context.status, Some(404));
+        assert_eq!(context.message.as_deref(), Some("model 'm' not found, try pulling it first"));
+    }
+
+    #[tokio::test]
+    async fn test_returned_error_carries_request_context() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/api/generate"))
+            .respond_with(
+                ResponseTemplate::new(500)
+                    .insert_header("x-request-id", "req-7")
+                    .set_body_json(serde_json::json!({"error": "boom"})),
+            )
+            .mount(&mock_server)
+            .await;
+
+        let config = ClientConfig {
+            base_url: mock_server.uri().parse().unwrap(),
+            max_retries: 0,
+            ..ClientConfig::default()
+        };
+        let client = HttpClient::new(config).unwrap();
+        let response = client
+            .post("api/generate")
+            .json(&serde_json::json!({"model": "m"}))
+            .model("m")
+            .send()
+            .await
+            .unwrap();
+        let error = client.error_for_response(response, Some("m")).await;
+
+        assert!(
+            matches!(error, OllamaError::ServerError { status: 500, ref message, .. } if message == "boom")
+        );
+        assert_eq!(error.method(), Some("POST"));
+        assert_eq!(error.endpoint(), Some("/api/generate"));
+        assert_eq!(error.model(), Some("m"));
+        assert_eq!(error.attempt(), Some(1));
+        assert!(error.elapsed().is_some());
+        assert_eq!(error.request_id(), Some("req-7"));
+        let display = error.to_string();
+        assert!(
+            display.starts_with(
+                "Server error: 500 - boom (POST /api/generate, model m, attempt 1, after "
+            ),
+            "{display}"
+        );
+        assert!(display.ends_with(", request id req-7)"), "{display}");
+    }
+
+    #[tokio::test]
+    async fn test_transport_error_carries_request_context() {
+        let config = ClientConfig {
+            base_url: "http://127.0.0.1:1".parse().unwrap(),
+            max_retries: 0,
+            ..ClientConfig::default()
+        };
+        let client = HttpClient::new(config).unwrap();
+
+        let error = client.get("api/tags").await.unwrap_err();
+
+        assert!(matches!(error, OllamaError::NetworkError(..)));
+        assert_eq!(error.method(), Some("GET"));
+        assert_eq!(error.endpoint(), Some("/api/tags"));
+        assert_eq!(error.model(), None);
+        assert!(error.to_string().contains(" (GET /api/tags, attempt 1, after "), "{error}");
+    }
+
+    #[tokio::test]
+    async fn test_classified_errors_keep_status_and_message() {
+        let cases = [
+            (401, "unauthorized", "AuthenticationError"),
+            (403, "forbidden", "AuthenticationError"),
+            (404, "model 'm' not found", "ModelNotFound"),
+            (503, "loading model", "ModelLoading"),
+            (500, "model requires more system memory than is available", "InsufficientResources"),
+        ];
+        for (status, message, variant) in cases {
+            let mock_server = MockServer::start().await;
+            Mock::given(method("POST"))
+                .and(path("/api/generate"))
+                .respond_with(
+                    ResponseTemplate::new(status)
+                        .set_body_json(serde_json::json!({"error": message})),
+                )
+                .mount(&mock_server)
+                .await;
+
+            let client = retrying_client(mock_server.uri(), 0);
+            let response = client.post("api/generate").json(&"{}").model("m").send().await.unwrap();
+            let error = client.error_for_response(response, Some("m")).await;
+
+            assert!(format!("{error:?}").starts_with(variant), "{error:?}");
+            assert_eq!(error.status_code(), Some(status), "{error}");
+            assert_eq!(error.server_message(), Some(message), "{error}");
+        }
+    }
 }
@@ -0,0 +1,248 @@
+//! Client-side concurrency and request-rate limits
+
+use crate::{models::model_name::ModelName, utils::middleware::map_body_stream};
+use futures_util::StreamExt;
+use reqwest::Response;
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+use tokio::{
+    sync::{OwnedSemaphorePermit, Semaphore},
+    time::Instant,
+};
+
+/// Limits applied by the client before sending each request attempt
+///
+/// In-flight limits count a request from the moment it is sent until its
+/// response body, including a stream, is fully read or dropped. The rate limit
+/// is a token bucket that refills at `requests_per_second` and holds up to
+/// `burst` tokens.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct RateLimits {
+    /// Maximum number of requests in flight across all models
+    pub max_in_flight: Option<usize>,
+    /// Maximum number of requests in flight for any single model
+    pub max_in_flight_per_model: Option<usize>,
+    /// Sustained request rate, in requests per second
+    pub requests_per_second: Option<f64>,
+    /// Number of requests that may be sent at once before the rate applies, defaults to 1
+    pub burst: Option<u32>,
+}
+
+impl RateLimits {
+    /// Whether any limit is configured
+    pub fn is_unlimited(&self) -> bool {
+        self.max_in_flight.is_none()
+            && self.max_in_flight_per_model.is_none()
+            && self.requests_per_second.is_none()
+    }
+}
+
+/// Shared limiter state enforcing [`RateLimits`] for one client
+#[derive(Debug)]
+pub(crate) struct Limiter {
+    global: Option<Arc<Semaphore>>,
+    per_model: Option<(usize, Mutex<HashMap<String, Arc<Semaphore>>>)>,
+    bucket: Option<TokenBucket>,
+}
+
+/// Permits held for the lifetime of one response
+pub(crate) struct Permits(Vec<OwnedSemaphorePermit>);
+
+impl Limiter {
+    pub(crate) fn new(limits: &RateLimits) -> Self {
+        Self {
+            global: limits.max_in_flight.map(|n| Arc::new(Semaphore::new(n.max(1)))),
+            per_model: limits.max_in_flight_per_model.map(|n| (n.max(1), Mutex::default())),
+            bucket: limits
+                .requests_per_second
+                .filter(|rate| *rate > 0.0)
+                .map(|rate| TokenBucket::new(rate, limits.burst.unwrap_or(1).max(1))),
+        }
+    }
+
+    /// Wait until the request may be sent, returning the permits it must hold
+    pub(crate) async fn acquire(&self, model: Option<&str>) -> Permits {
+        let mut permits = Vec::new();
+        if let Some(semaphore) = self.model_semaphore(model) {
+            permits.push(semaphore.acquire_owned().await.expect("limiter semaphore closed"));
+        }
+        if let Some(global) = &self.global {
+            permits.push(global.clone().acquire_owned().await.expect("limiter semaphore closed"));
+        }
+        if let Some(bucket) = &self.bucket {
+            bucket.acquire().await;
+        }
+        Permits(permits)
+    }
+
+    fn model_semaphore(&self, model: Option<&str>) -> Option<Arc<Semaphore>> {
+        let (limit, semaphores) = self.per_model.as_ref()?;
+        // Key by the shortest form so `llama3` and `library/llama3:latest` share a limit
+        let model = model?;
+        let model = ModelName::parse(model).map_or_else(|_| model.to_string(), |n| n.to_string());
+        let mut semaphores = semaphores.lock().expect("limiter lock poisoned");
+        if let Some(semaphore) = semaphores.get(&model) {
+            return Some(semaphore.clone());
+        }
+
+        // Only models with requests in flight or waiting are kept; permits and
+        // waiters hold their own reference, so an unreferenced semaphore is idle
+        semaphores.retain(|_, semaphore| Arc::strong_count(semaphore) > 1);
+        let semaphore = Arc::new(Semaphore::new(*limit));
+        semaphores.insert(model, semaphore.clone());
+        Some(semaphore)
+    }
+}
+
+impl Permits {
+    /// Keep the permits until the response body is read to the end or dropped
+    pub(crate) fn hold_until_body_done(self, response: Response) -> Response {
+        if self.0.is_empty() {
+            return response;
+        }
+        map_body_stream(response, move |body| {
+            body.map(move |chunk| {
+                let _held = &self;
+                chunk
+            })
+        })
+    }
+}
+
+/// Token bucket that hands out tokens in arrival order
+#[derive(Debug)]
+struct TokenBucket {
+    rate: f64,
+    burst: f64,
+    state: Mutex<(f64, Instant)>,
+}
+
+impl TokenBucket {
+    fn new(rate: f64, burst: u32) -> Self {
+        let burst = f64::from(burst);
+        Self { rate, burst, state: Mutex::new((burst, Instant::now())) }
+    }
+
+    async fn acquire(&self) {
+        let wait = {
+            let mut state = self.state.lock().expect("token bucket lock poisoned");
+            let (tokens, last) = &mut *state;
+            let now = Instant::now();
+            *tokens =
+                (*tokens + now.duration_since(*last).as_secs_f64() * self.rate).min(self.burst);
+            *last = now;
+
+            // Reserve a token even when the bucket is empty so waiters queue in order
+            *tokens -= 1.0;
+            (*tokens < 0.0).then(|| Duration::from_secs_f64(-*tokens / self.rate))
+        };
+
+        if let Some(wait) = wait {
+            let reservation = Reservation(Some(self));
+            tokio::time::sleep(wait).await;
+            reservation.keep();
+        }
+    }
+
+    fn refund(&self) {
+        let mut state = self.state.lock().expect("token bucket lock poisoned");
+        state.0 = (state.0 + 1.0).min(self.burst);
+    }
+}
+
+/// A token reserved ahead of time, returned to the bucket if the wait is abandoned
+struct Reservation<'a>(Option<&'a TokenBucket>);
+
+impl Reservation<'_> {
+    /// Spend the token
+    fn keep(mut self) {
+        self.0 = None;
+    }
+}
+
+impl Drop for Reservation<'_> {
+    fn drop(&mut self) {
+        if let Some(bucket) = self.0 {
+            bucket.refund();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test(start_paused = true)]
+    async fn test_token_bucket_allows_burst_then_paces() {
+        let bucket = TokenBucket::new(2.0, 3);
+        let started = Instant::now();
+
+        for _ in 0..3 {
+            bucket.acquire().await;
+        }
+        assert_eq!(started.elapsed(), Duration::ZERO);
+
+        bucket.acquire().await;
+        bucket.acquire().await;
+        assert_eq!(started.elapsed(), Duration::from_secs(1));
+    }
+
+    #[tokio::test]
+    async fn test_per_model_limit_is_independent() {
+        let limiter =
+            Limiter::new(&RateLimits { max_in_flight_per_model: Some(1), ..Default::default() });
+
+        let held = limiter.acquire(Some("a")).await;
+        let other = limiter.acquire(Some("b")).await;
+        let blocked = tokio::time::timeout(Duration::from_millis(50), limiter.acquire(Some("a")));
+        assert!(blocked.await.is_err());
+
+        drop((held, other));
+        let _ = limiter.acquire(Some("a")).await;
+        let _ = limiter.acquire(None).await;
+    }
+
+    #[tokio::test]
+    async fn test_model_shorthands_share_a_limit() {
+        let limiter =
+            Limiter::new(&RateLimits { max_in_flight_per_model: Some(1), ..Default::default() });
+
+        let held = limiter.acquire(Some("llama3")).await;
+        let blocked = limiter.acquire(Some("registry.ollama.ai/library/llama3:latest"));
+        assert!(tokio::time::timeout(Duration::from_millis(50), blocked).await.is_err());
+
+        drop(held);
+        let _ = limiter.acquire(Some("library/llama3:latest")).await;
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_abandoned_wait_refunds_token() {
+        let bucket = TokenBucket::new(1.0, 1);
+        let started = Instant::now();
+
+        bucket.acquire().await;
+        let abandoned = tokio::time::timeout(Duration::from_millis(100), bucket.acquire());
+        assert!(abandoned.await.is_err());
+
+        bucket.acquire().await;
+        assert_eq!(started.elapsed(), Duration::from_secs(1));
+    }
+
+    #[tokio::test]
+    async fn test_idle_model_semaphores_are_evicted() {
+        let limiter =
+            Limiter::new(&RateLimits { max_in_flight_per_model: Some(1), ..Default::default() });
+        let semaphores = || limiter.per_model.as_ref().unwrap().1.lock().unwrap().len();
+
+        let held = limiter.acquire(Some("a")).await;
+        drop(limiter.acquire(Some("b")).await);
+        assert_eq!(semaphores(), 2);
+
+        drop(limiter.acquire(Some("c")).await);
+        assert_eq!(semaphores(), 2);
+        drop(held);
+    }
+}
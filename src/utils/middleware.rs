@@ -0,0 +1,277 @@
+//! Request and response middleware
+
+use crate::{error::Result, utils::transport::Transport};
+use bytes::Bytes;
+use futures_util::{Stream, future::BoxFuture};
+use reqwest::{Body, Request, Response, ResponseBuilderExt};
+use std::{error::Error, fmt, pin::Pin, sync::Arc};
+
+/// Raw response body chunks as received from the transport
+pub type BodyStream = Pin<Box<dyn Stream<Item = reqwest::Result<Bytes>> + Send>>;
+
+/// Intercepts every request attempt sent by [`HttpClient`](super::http::HttpClient)
+///
+/// Middleware registered through
+/// [`ClientConfigBuilder::middleware`](crate::ClientConfigBuilder::middleware)
+/// runs in registration order around each attempt, including retries. Each
+/// middleware may modify the request, call [`Next::run`] to continue the chain,
+/// and modify the response it gets back. Returning without calling `next`
+/// short-circuits the chain, for example to serve a cached response.
+pub trait Middleware: Send + Sync + fmt::Debug {
+    /// Handle a request, usually by delegating to `next`
+    fn handle<'a>(&'a self, request: Request, next: Next<'a>) -> BoxFuture<'a, Result<Response>>;
+}
+
+impl<M: Middleware + ?Sized> Middleware for Arc<M> {
+    fn handle<'a>(&'a self, request: Request, next: Next<'a>) -> BoxFuture<'a, Result<Response>> {
+        (**self).handle(request, next)
+    }
+}
+
+/// The remainder of a middleware chain, ending in the transport
+#[derive(Clone, Copy)]
+pub struct Next<'a> {
+    middleware: &'a [Arc<dyn Middleware>],
+    transport: &'a dyn Transport,
+}
+
+impl<'a> Next<'a> {
+    pub(crate) fn new(middleware: &'a [Arc<dyn Middleware>], transport: &'a dyn Transport) -> Self {
+        Self { middleware, transport }
+    }
+
+    /// Pass the request to the next middleware, or to the transport at the end of the chain
+    pub fn run(self, request: Request) -> BoxFuture<'a, Result<Response>> {
+        match self.middleware.split_first() {
+            Some((middleware, rest)) => {
+                middleware.handle(request, Self { middleware: rest, ..self })
+            }
+            None => self.transport.execute(request),
+        }
+    }
+}
+
+impl fmt::Debug for Next<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Next")
+            .field("remaining", &self.middleware.len())
+            .field("transport", &self.transport)
+            .finish()
+    }
+}
+
+/// Replace a response body while keeping its status, version, headers and URL
+///
+/// The closure receives the original body as a stream of chunks, so streaming
+/// responses can be observed or rewritten as they arrive without buffering.
+pub fn map_body_stream<F, S, E>(response: Response, f: F) -> Response
+where
+    F: FnOnce(BodyStream) -> S,
+    S: Stream<Item = std::result::Result<Bytes, E>> + Send + 'static,
+    E: Into<Box<dyn Error + Send + Sync>> + 'static,
+{
+    let mut builder = http::Response::builder()
+        .status(response.status())
+        .version(response.version())
+        .url(response.url().clone());
+    if let Some(headers) = builder.headers_mut() {
+        *headers = response.headers().clone();
+    }
+
+    let body = Body::wrap_stream(f(Box::pin(response.bytes_stream())));
+    builder.body(body).expect("parts copied from a valid response").into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ClientConfig, OllamaError, utils::http::HttpClient};
+    use futures_util::TryStreamExt;
+    use serde_json::{Value, json};
+    use std::sync::Mutex;
+    use wiremock::{
+        Mock, MockServer, ResponseTemplate,
+        matchers::{body_json, headers, method, path},
+    };
+
+    #[derive(Debug)]
+    struct Tag {
+        name: &'static str,
+        log: Arc<Mutex<Vec<String>>>,
+    }
+
+    impl Middleware for Tag {
+        fn handle<'a>(
+            &'a self,
+            mut request: Request,
+            next: Next<'a>,
+        ) -> BoxFuture<'a, Result<Response>> {
+            Box::pin(async move {
+                self.log.lock().unwrap().push(format!("{} request", self.name));
+                request.headers_mut().append("x-chain", self.name.parse().unwrap());
+                let response = next.run(request).await;
+                self.log.lock().unwrap().push(format!("{} response", self.name));
+                response
+            })
+        }
+    }
+
+    #[derive(Debug)]
+    struct RewriteModel;
+
+    impl Middleware for RewriteModel {
+        fn handle<'a>(
+            &'a self,
+            mut request: Request,
+            next: Next<'a>,
+        ) -> BoxFuture<'a, Result<Response>> {
+            Box::pin(async move {
+                let bytes = request.body().and_then(Body::as_bytes).unwrap_or_default();
+                let mut body: Value = serde_json::from_slice(bytes)?;
+                body["model"] = json!("llama3:8b");
+                *request.body_mut() = Some(serde_json::to_vec(&body)?.into());
+                next.run(request).await
+            })
+        }
+    }
+
+    #[derive(Debug)]
+    struct Cached;
+
+    impl Middleware for Cached {
+        fn handle<'a>(&'a self, _: Request, _: Next<'a>) -> BoxFuture<'a, Result<Response>> {
+            let response = http::Response::builder().status(200).body(r#"{"models":[]}"#);
+            Box::pin(async move { Ok(response.unwrap().into()) })
+        }
+    }
+
+    #[derive(Debug)]
+    struct UppercaseBody;
+
+    impl Middleware for UppercaseBody {
+        fn handle<'a>(
+            &'a self,
+            request: Request,
+            next: Next<'a>,
+        ) -> BoxFuture<'a, Result<Response>> {
+            Box::pin(async move {
+                let response = next.run(request).await?;
+                Ok(map_body_stream(response, |body| {
+                    body.map_ok(|chunk| Bytes::from(chunk.to_ascii_uppercase()))
+                }))
+            })
+        }
+    }
+
+    fn client(uri: String, middleware: Vec<Arc<dyn Middleware>>) -> HttpClient {
+        let mut builder = ClientConfig::builder().base_url(uri).max_retries(0);
+        for middleware in middleware {
+            builder = builder.middleware(middleware);
+        }
+        HttpClient::new(builder.build().unwrap()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_middleware_runs_in_registration_order() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/api/tags"))
+            .and(headers("x-chain", vec!["outer", "inner"]))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let client = client(
+            mock_server.uri(),
+            vec![
+                Arc::new(Tag { name: "outer", log: log.clone() }),
+                Arc::new(Tag { name: "inner", log: log.clone() }),
+            ],
+        );
+        client.get("api/tags").await.unwrap();
+
+        assert_eq!(
+            *log.lock().unwrap(),
+            vec!["outer request", "inner request", "inner response", "outer response"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_middleware_rewrites_request_body() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/api/show"))
+            .and(body_json(json!({"model": "llama3:8b"})))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let client = client(mock_server.uri(), vec![Arc::new(RewriteModel)]);
+        let response =
+            client.post("api/show").json(&json!({"model": "llama3"})).send().await.unwrap();
+
+        assert_eq!(response.status().as_u16(), 200);
+    }
+
+    #[tokio::test]
+    async fn test_middleware_can_short_circuit() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/api/tags"))
+            .respond_with(ResponseTemplate::new(500))
+            .expect(0)
+            .mount(&mock_server)
+            .await;
+
+        let client = client(mock_server.uri(), vec![Arc::new(Cached)]);
+        let response = client.get("api/tags").await.unwrap();
+        let body: Value = client.json(response).await.unwrap();
+
+        assert_eq!(body, json!({"models": []}));
+    }
+
+    #[tokio::test]
+    async fn test_middleware_rewrites_streaming_response() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/api/pull"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("x-request-id", "abc")
+                    .set_body_string("{\"status\":\"pulling\"}\n{\"status\":\"success\"}\n"),
+            )
+            .mount(&mock_server)
+            .await;
+
+        let client = client(mock_server.uri(), vec![Arc::new(UppercaseBody)]);
+        let response = client.post("api/pull").json(&json!({})).send().await.unwrap();
+        assert_eq!(response.headers()["x-request-id"], "abc");
+
+        let items: Vec<Value> = client.ndjson_stream(response).try_collect().await.unwrap();
+        assert_eq!(items, vec![json!({"STATUS": "PULLING"}), json!({"STATUS": "SUCCESS"})]);
+    }
+
+    #[tokio::test]
+    async fn test_middleware_errors_propagate() {
+        #[derive(Debug)]
+        struct Deny;
+
+        impl Middleware for Deny {
+            fn handle<'a>(&'a self, _: Request, _: Next<'a>) -> BoxFuture<'a, Result<Response>> {
+                Box::pin(async { Err(OllamaError::ConfigError("denied".to_string())) })
+            }
+        }
+
+        let client = client("http://localhost:11434".to_string(), vec![Arc::new(Deny)]);
+        let result = client.get("api/tags").await;
+
+        assert!(matches!(result, Err(OllamaError::ConfigError(msg)) if msg == "denied"));
+    }
+}
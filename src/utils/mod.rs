@@ -1,3 +1,11 @@
 //! Utility modules
 
+pub mod auth;
+pub mod breaker;
+pub mod cancel;
+pub(crate) mod capabilities;
 pub mod http;
+pub mod limit;
+pub mod middleware;
+pub mod retry;
+pub mod transport;
@@ -0,0 +1,205 @@
+//! Retry policy and backoff helpers
+
+use crate::config::ClientConfig;
+use reqwest::header::{HeaderMap, RETRY_AFTER};
+use std::{
+    collections::hash_map::RandomState,
+    hash::{BuildHasher, Hasher},
+    time::{Duration, SystemTime},
+};
+
+/// Upper bound applied to a single computed backoff delay
+pub const MAX_BACKOFF: Duration = Duration::from_secs(30);
+
+/// Retry policy used by [`HttpClient`](crate::utils::http::HttpClient)
+///
+/// Delays grow exponentially from `base_delay` and use "equal jitter": half of the
+/// computed delay is fixed and the other half is randomized so that concurrent
+/// clients do not retry in lockstep.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Maximum number of retries after the first attempt
+    pub max_retries: u32,
+    /// Base delay for the first retry
+    pub base_delay: Duration,
+    /// Upper bound for a single backoff delay
+    pub max_delay: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self::from_config(&ClientConfig::default())
+    }
+}
+
+impl RetryPolicy {
+    /// Create a retry policy from the client configuration
+    pub fn from_config(config: &ClientConfig) -> Self {
+        Self {
+            max_retries: config.max_retries,
+            base_delay: config.retry_delay,
+            max_delay: MAX_BACKOFF.max(config.retry_delay),
+        }
+    }
+
+    /// Create a policy that never retries
+    pub fn none() -> Self {
+        Self { max_retries: 0, ..Self::default() }
+    }
+
+    /// Exponential backoff delay with jitter for the given zero-based retry number
+    pub fn backoff(&self, retry: u32) -> Duration {
+        let factor = 2u32.saturating_pow(retry.min(16));
+        let delay = self.base_delay.saturating_mul(factor).min(self.max_delay);
+        let half = delay / 2;
+        half + half.mul_f64(jitter_fraction())
+    }
+
+    /// Delay before the given retry, preferring a server-provided `Retry-After` value
+    ///
+    /// Returns `None` when the server asks the client to wait longer than `max_delay`,
+    /// in which case the response is surfaced instead of sleeping.
+    pub fn delay_for(&self, retry: u32, retry_after: Option<Duration>) -> Option<Duration> {
+        match retry_after {
+            Some(delay) if delay > self.max_delay => None,
+            Some(delay) => Some(delay),
+            None => Some(self.backoff(retry)),
+        }
+    }
+}
+
+/// Reconnect budget of a resilient pull
+///
+/// [`ModelsApi::pull_model_resilient`](crate::api::models::ModelsApi::pull_model_resilient)
+/// waits out `policy`'s backoff between reconnects and gives up once
+/// `policy.max_retries` reconnects in a row complete no bytes, or once it has
+/// reconnected `max_reconnects` times in total. A [`RetryPolicy`] converts into
+/// these options with the default total.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ResilientPullOptions {
+    /// Backoff between reconnects and the reconnects allowed without progress
+    pub policy: RetryPolicy,
+    /// Most reconnects in total, however much progress each one makes
+    pub max_reconnects: u32,
+}
+
+impl ResilientPullOptions {
+    /// Default for [`max_reconnects`](Self::max_reconnects)
+    pub const DEFAULT_MAX_RECONNECTS: u32 = 100;
+}
+
+impl Default for ResilientPullOptions {
+    fn default() -> Self {
+        RetryPolicy::default().into()
+    }
+}
+
+impl From<RetryPolicy> for ResilientPullOptions {
+    fn from(policy: RetryPolicy) -> Self {
+        Self { policy, max_reconnects: Self::DEFAULT_MAX_RECONNECTS }
+    }
+}
+
+/// Parse a `Retry-After` header expressed either in seconds or as an HTTP date
+pub fn parse_retry_after(headers: &HeaderMap) -> Option<Duration> {
+    let value = headers.get(RETRY_AFTER)?.to_str().ok()?.trim();
+
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+
+    let date = chrono::DateTime::parse_from_rfc2822(value).ok()?;
+    let target = SystemTime::from(date);
+    Some(target.duration_since(SystemTime::now()).unwrap_or(Duration::ZERO))
+}
+
+/// Pseudo-random value in `[0, 1)` used to spread retries
+fn jitter_fraction() -> f64 {
+    let mut hasher = RandomState::new().build_hasher();
+    hasher.write_u128(
+        SystemTime::now().duration_since(SystemTime::UNIX_EPOCH).unwrap_or_default().as_nanos(),
+    );
+    (hasher.finish() >> 11) as f64 / (1u64 << 53) as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::HeaderValue;
+
+    fn policy() -> RetryPolicy {
+        RetryPolicy {
+            max_retries: 3,
+            base_delay: Duration::from_millis(100),
+            max_delay: Duration::from_secs(1),
+        }
+    }
+
+    #[test]
+    fn test_backoff_grows_and_is_capped() {
+        let policy = policy();
+
+        for retry in 0..6 {
+            let expected = Duration::from_millis(100 * 2u64.pow(retry)).min(policy.max_delay);
+            let delay = policy.backoff(retry);
+            assert!(delay >= expected / 2, "retry {retry}: {delay:?} < {expected:?} / 2");
+            assert!(delay <= expected, "retry {retry}: {delay:?} > {expected:?}");
+        }
+    }
+
+    #[test]
+    fn test_none_policy() {
+        assert_eq!(RetryPolicy::none().max_retries, 0);
+    }
+
+    #[test]
+    fn test_policy_from_config() {
+        let config = ClientConfig::builder()
+            .max_retries(5)
+            .retry_delay(Duration::from_millis(250))
+            .build()
+            .unwrap();
+        let policy = RetryPolicy::from_config(&config);
+
+        assert_eq!(policy.max_retries, 5);
+        assert_eq!(policy.base_delay, Duration::from_millis(250));
+        assert_eq!(policy.max_delay, MAX_BACKOFF);
+    }
+
+    #[test]
+    fn test_parse_retry_after_seconds() {
+        let mut headers = HeaderMap::new();
+        headers.insert(RETRY_AFTER, HeaderValue::from_static("7"));
+
+        assert_eq!(parse_retry_after(&headers), Some(Duration::from_secs(7)));
+    }
+
+    #[test]
+    fn test_parse_retry_after_http_date_in_past() {
+        let mut headers = HeaderMap::new();
+        headers.insert(RETRY_AFTER, HeaderValue::from_static("Wed, 21 Oct 2015 07:28:00 GMT"));
+
+        assert_eq!(parse_retry_after(&headers), Some(Duration::ZERO));
+    }
+
+    #[test]
+    fn test_parse_retry_after_invalid() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(parse_retry_after(&headers), None);
+
+        headers.insert(RETRY_AFTER, HeaderValue::from_static("soon"));
+        assert_eq!(parse_retry_after(&headers), None);
+    }
+
+    #[test]
+    fn test_delay_for_prefers_retry_after() {
+        let policy = policy();
+
+        assert_eq!(
+            policy.delay_for(0, Some(Duration::from_millis(300))),
+            Some(Duration::from_millis(300))
+        );
+        assert_eq!(policy.delay_for(0, Some(Duration::from_secs(60))), None);
+        assert!(policy.delay_for(1, None).is_some());
+    }
+}
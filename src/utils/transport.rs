@@ -0,0 +1,468 @@
+//! Pluggable HTTP transport
+
+use crate::{
+    config::ClientConfig,
+    error::{OllamaError, Result},
+};
+use futures_util::future::BoxFuture;
+use reqwest::{Client, Request, Response};
+use std::{fmt, sync::Arc};
+
+/// Executes a single HTTP exchange on behalf of [`HttpClient`](super::http::HttpClient)
+///
+/// `HttpClient` builds every request, applies retries and cancellation, and then
+/// hands each attempt to its transport. Implement this trait to route requests
+/// through custom networking or to serve responses from memory in tests.
+pub trait Transport: Send + Sync + fmt::Debug {
+    /// Send the request and return the response headers, leaving the body unread
+    fn execute(&self, request: Request) -> BoxFuture<'_, Result<Response>>;
+}
+
+impl Transport for Client {
+    fn execute(&self, request: Request) -> BoxFuture<'_, Result<Response>> {
+        Box::pin(async move {
+            Client::execute(self, request).await.map_err(|e| {
+                match (e.is_connect(), e.is_timeout()) {
+                    (true, true) => OllamaError::ConnectTimeout(None),
+                    (false, true) => OllamaError::Timeout(None),
+                    _ => OllamaError::NetworkError(e, None),
+                }
+            })
+        })
+    }
+}
+
+impl<T: Transport + ?Sized> Transport for Arc<T> {
+    fn execute(&self, request: Request) -> BoxFuture<'_, Result<Response>> {
+        (**self).execute(request)
+    }
+}
+
+/// Build the default reqwest transport for a configuration
+pub fn default_client(config: &ClientConfig) -> Result<Client> {
+    // The whole-request timeout is applied per request so that streams can opt out
+    let mut client_builder = Client::builder().user_agent(&config.user_agent);
+
+    if let Some(connect_timeout) = config.connect_timeout {
+        client_builder = client_builder.connect_timeout(connect_timeout);
+    }
+
+    if config.follow_redirects {
+        client_builder = client_builder.redirect(reqwest::redirect::Policy::limited(10));
+    } else {
+        client_builder = client_builder.redirect(reqwest::redirect::Policy::none());
+    }
+
+    if let Some(proxy) = &config.proxy {
+        let proxy = reqwest::Proxy::all(proxy.as_str())
+            .map_err(|e| OllamaError::ConfigError(format!("Invalid proxy URL '{proxy}': {e}")))?
+            .no_proxy(config.no_proxy.as_deref().and_then(reqwest::NoProxy::from_string));
+        client_builder = client_builder.proxy(proxy);
+    }
+
+    #[cfg(feature = "tls")]
+    {
+        client_builder = configure_tls(client_builder, config)?;
+    }
+
+    if let Some(path) = &config.unix_socket {
+        #[cfg(unix)]
+        {
+            client_builder = client_builder.unix_socket(path.clone());
+        }
+        #[cfg(not(unix))]
+        return Err(OllamaError::ConfigError(format!(
+            "Unix domain socket '{}' is not supported on this platform",
+            path.display()
+        )));
+    }
+
+    client_builder
+        .build()
+        .map_err(|e| OllamaError::ConfigError(format!("Failed to create HTTP client: {e}")))
+}
+
+/// Apply the custom CA bundle and client identity from the configuration
+#[cfg(feature = "tls")]
+fn configure_tls(
+    mut client_builder: reqwest::ClientBuilder,
+    config: &ClientConfig,
+) -> Result<reqwest::ClientBuilder> {
+    if let Some(path) = &config.ca_bundle {
+        let certificates =
+            reqwest::Certificate::from_pem_bundle(&read_pem(path)?).map_err(|e| {
+                OllamaError::ConfigError(format!("Invalid CA bundle '{}': {e}", path.display()))
+            })?;
+        client_builder = client_builder.tls_certs_merge(certificates);
+    }
+
+    if let Some(cert) = &config.client_cert {
+        let mut pem = read_pem(cert)?;
+        if let Some(key) = &config.client_key {
+            pem.push(b'\n');
+            pem.extend(read_pem(key)?);
+        }
+        let identity = reqwest::Identity::from_pem(&pem).map_err(|e| {
+            OllamaError::ConfigError(format!(
+                "Invalid client certificate '{}': {e}",
+                cert.display()
+            ))
+        })?;
+        client_builder = client_builder.identity(identity);
+    }
+
+    Ok(client_builder)
+}
+
+#[cfg(feature = "tls")]
+fn read_pem(path: &std::path::Path) -> Result<Vec<u8>> {
+    std::fs::read(path)
+        .map_err(|e| OllamaError::ConfigError(format!("Failed to read '{}': {e}", path.display())))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{ClientConfig, OllamaClient, OllamaError};
+    use std::{io::BufRead, time::Duration};
+    use wiremock::{
+        Mock, MockServer, ResponseTemplate,
+        matchers::{method, path},
+    };
+
+    /// Read one HTTP/1.1 request and return its request line
+    fn read_request(reader: &mut impl BufRead) -> std::io::Result<String> {
+        let mut request_line = String::new();
+        reader.read_line(&mut request_line)?;
+        let mut content_length = 0;
+        loop {
+            let mut line = String::new();
+            reader.read_line(&mut line)?;
+            if line.trim().is_empty() {
+                break;
+            }
+            if let Some(value) = line.to_ascii_lowercase().strip_prefix("content-length:") {
+                content_length = value.trim().parse().unwrap();
+            }
+        }
+        reader.read_exact(&mut vec![0; content_length])?;
+        Ok(request_line)
+    }
+
+    fn json_response(body: &str) -> String {
+        format!(
+            "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\n\
+             Content-Length: {}\r\nConnection: close\r\n\r\n{body}",
+            body.len()
+        )
+    }
+
+    async fn version_server() -> MockServer {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api/version"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(serde_json::json!({"version": "0.6.0"})),
+            )
+            .mount(&mock_server)
+            .await;
+        mock_server
+    }
+
+    #[tokio::test]
+    async fn test_requests_through_proxy() {
+        let proxy = version_server().await;
+        let config = ClientConfig::builder()
+            .base_url("http://ollama.invalid:11434")
+            .proxy(proxy.uri())
+            .max_retries(0)
+            .build()
+            .unwrap();
+        let client = OllamaClient::with_config(config).unwrap();
+
+        assert_eq!(client.version().await.unwrap()["version"], "0.6.0");
+        let received = proxy.received_requests().await.unwrap();
+        assert_eq!(received[0].url.host_str(), Some("ollama.invalid"));
+    }
+
+    #[tokio::test]
+    async fn test_no_proxy_bypasses_proxy() {
+        let server = version_server().await;
+        let closed = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let unreachable_proxy = format!("http://{}", closed.local_addr().unwrap());
+        drop(closed);
+
+        let config = ClientConfig::builder()
+            .base_url(server.uri())
+            .proxy(unreachable_proxy)
+            .no_proxy("127.0.0.1,localhost")
+            .max_retries(0)
+            .build()
+            .unwrap();
+        let client = OllamaClient::with_config(config).unwrap();
+
+        assert_eq!(client.version().await.unwrap()["version"], "0.6.0");
+    }
+
+    #[tokio::test]
+    async fn test_connect_timeout() {
+        // A listener whose accept queue is full drops further connection attempts
+        let socket = tokio::net::TcpSocket::new_v4().unwrap();
+        socket.bind("127.0.0.1:0".parse().unwrap()).unwrap();
+        let listener = socket.listen(1).unwrap();
+        let addr = listener.local_addr().unwrap();
+        let mut queued = Vec::new();
+        while let Ok(stream) =
+            std::net::TcpStream::connect_timeout(&addr, Duration::from_millis(100))
+        {
+            queued.push(stream);
+            assert!(queued.len() < 64, "accept queue never filled");
+        }
+
+        let config = ClientConfig::builder()
+            .base_url(format!("http://{addr}"))
+            .connect_timeout(Duration::from_millis(100))
+            .max_retries(0)
+            .build()
+            .unwrap();
+        let client = OllamaClient::with_config(config).unwrap();
+        let error = client.version().await.unwrap_err();
+
+        assert!(matches!(error, OllamaError::ConnectTimeout(_)), "{error:?}");
+        assert!(error.is_retryable());
+    }
+
+    #[test]
+    fn test_invalid_proxy_url() {
+        let config = ClientConfig::builder().proxy("not a url").build().unwrap();
+        let result = OllamaClient::with_config(config);
+
+        assert!(matches!(result, Err(OllamaError::ConfigError(_))));
+    }
+
+    #[cfg(unix)]
+    mod unix_socket {
+        use super::*;
+        use std::{
+            io::{BufReader, Write},
+            os::unix::net::UnixListener,
+            path::Path,
+        };
+        use tokio_stream::StreamExt;
+
+        /// Serve canned responses on a Unix socket, one request per connection
+        fn unix_socket_server(path: &Path) {
+            let listener = UnixListener::bind(path).unwrap();
+
+            std::thread::spawn(move || {
+                for socket in listener.incoming() {
+                    let Ok(mut socket) = socket else { break };
+                    let mut reader = BufReader::new(socket.try_clone().unwrap());
+                    let request_line = read_request(&mut reader).unwrap();
+
+                    let records = [
+                        "{\"model\":\"m\",\"response\":\"Hello\",\"done\":false}\n",
+                        "{\"model\":\"m\",\"response\":\" socket\",\"done\":true}\n",
+                    ];
+                    let response = if request_line.starts_with("GET /api/tags ") {
+                        json_response("{\"models\":[]}")
+                    } else if request_line.starts_with("POST /api/generate ") {
+                        let chunks: String = records
+                            .iter()
+                            .map(|record| format!("{:x}\r\n{record}\r\n", record.len()))
+                            .collect();
+                        format!(
+                            "HTTP/1.1 200 OK\r\nContent-Type: application/x-ndjson\r\n\
+                             Transfer-Encoding: chunked\r\nConnection: close\r\n\r\n\
+                             {chunks}0\r\n\r\n"
+                        )
+                    } else {
+                        "HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n"
+                            .to_string()
+                    };
+                    socket.write_all(response.as_bytes()).unwrap();
+                }
+            });
+        }
+
+        #[tokio::test]
+        async fn test_requests_over_unix_socket_url() {
+            let dir = tempfile::tempdir().unwrap();
+            let socket = dir.path().join("ollama.sock");
+            unix_socket_server(&socket);
+
+            let client = OllamaClient::new(format!("unix://{}", socket.display())).unwrap();
+            let models = client.list_models().await.unwrap();
+
+            assert!(models.models.is_empty());
+        }
+
+        #[tokio::test]
+        async fn test_streaming_over_unix_socket_option() {
+            let dir = tempfile::tempdir().unwrap();
+            let socket = dir.path().join("ollama.sock");
+            unix_socket_server(&socket);
+
+            let config = ClientConfig::builder().unix_socket(&socket).build().unwrap();
+            let client = OllamaClient::with_config(config).unwrap();
+            let stream = client.generate().model("m").prompt("hi").stream().await.unwrap();
+            let text: Vec<String> = stream.map(|chunk| chunk.unwrap().response).collect().await;
+
+            assert_eq!(text, vec!["Hello", " socket"]);
+        }
+    }
+
+    #[cfg(feature = "tls")]
+    mod tls {
+        use super::*;
+        use rcgen::{
+            BasicConstraints, CertificateParams, CertifiedIssuer, DnType, ExtendedKeyUsagePurpose,
+            IsCa, KeyPair,
+        };
+        use rustls::{
+            RootCertStore, ServerConfig, ServerConnection, StreamOwned, pki_types::PrivateKeyDer,
+            server::WebPkiClientVerifier,
+        };
+        use std::{
+            io::{BufReader, Write},
+            net::TcpListener,
+            path::PathBuf,
+            sync::Arc,
+        };
+        use tempfile::TempDir;
+
+        /// A throwaway CA with a server and a client certificate, written to disk as PEM
+        struct TestPki {
+            dir: TempDir,
+            ca: CertifiedIssuer<'static, KeyPair>,
+            server_chain: Vec<rustls::pki_types::CertificateDer<'static>>,
+            server_key: Vec<u8>,
+        }
+
+        impl TestPki {
+            fn generate() -> Self {
+                let mut ca_params = CertificateParams::new(Vec::<String>::new()).unwrap();
+                ca_params.is_ca = IsCa::Ca(BasicConstraints::Unconstrained);
+                ca_params.distinguished_name.push(DnType::CommonName, "SDK Test CA");
+                let ca =
+                    CertifiedIssuer::self_signed(ca_params, KeyPair::generate().unwrap()).unwrap();
+
+                let server_key = KeyPair::generate().unwrap();
+                let mut server_params = CertificateParams::new(vec!["localhost".into()]).unwrap();
+                server_params.extended_key_usages = vec![ExtendedKeyUsagePurpose::ServerAuth];
+                let server_cert = server_params.signed_by(&server_key, &*ca).unwrap();
+
+                let client_key = KeyPair::generate().unwrap();
+                let mut client_params = CertificateParams::new(vec!["client".into()]).unwrap();
+                client_params.extended_key_usages = vec![ExtendedKeyUsagePurpose::ClientAuth];
+                let client_cert = client_params.signed_by(&client_key, &*ca).unwrap();
+
+                let dir = tempfile::tempdir().unwrap();
+                std::fs::write(dir.path().join("ca.pem"), ca.pem()).unwrap();
+                std::fs::write(dir.path().join("client.crt"), client_cert.pem()).unwrap();
+                std::fs::write(dir.path().join("client.key"), client_key.serialize_pem()).unwrap();
+                std::fs::write(
+                    dir.path().join("client.pem"),
+                    format!("{}{}", client_cert.pem(), client_key.serialize_pem()),
+                )
+                .unwrap();
+
+                Self {
+                    dir,
+                    server_chain: vec![server_cert.der().clone(), ca.der().clone()],
+                    ca,
+                    server_key: server_key.serialize_der(),
+                }
+            }
+
+            fn path(&self, name: &str) -> PathBuf {
+                self.dir.path().join(name)
+            }
+
+            /// Start an HTTPS server answering `/api/version`, optionally requiring a client certificate
+            fn serve(&self, require_client_cert: bool) -> String {
+                let builder = ServerConfig::builder();
+                let builder = if require_client_cert {
+                    let mut roots = RootCertStore::empty();
+                    roots.add(self.ca.der().clone()).unwrap();
+                    let verifier = WebPkiClientVerifier::builder(Arc::new(roots)).build().unwrap();
+                    builder.with_client_cert_verifier(verifier)
+                } else {
+                    builder.with_no_client_auth()
+                };
+                let key = PrivateKeyDer::try_from(self.server_key.clone()).unwrap();
+                let config =
+                    Arc::new(builder.with_single_cert(self.server_chain.clone(), key).unwrap());
+
+                let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+                let port = listener.local_addr().unwrap().port();
+                std::thread::spawn(move || {
+                    for tcp in listener.incoming() {
+                        let Ok(tcp) = tcp else { break };
+                        let connection = ServerConnection::new(config.clone()).unwrap();
+                        let mut tls = StreamOwned::new(connection, tcp);
+
+                        // Handshake failures surface as read errors
+                        if read_request(&mut BufReader::new(&mut tls)).is_err() {
+                            continue;
+                        }
+                        let _ = tls.write_all(json_response("{\"version\":\"0.6.0\"}").as_bytes());
+                        tls.conn.send_close_notify();
+                        let _ = tls.flush();
+                    }
+                });
+
+                format!("https://localhost:{port}")
+            }
+        }
+
+        fn client(config: crate::ClientConfigBuilder) -> OllamaClient {
+            OllamaClient::with_config(config.max_retries(0).build().unwrap()).unwrap()
+        }
+
+        #[tokio::test]
+        async fn test_custom_ca_bundle() {
+            let pki = TestPki::generate();
+            let uri = pki.serve(false);
+
+            let untrusted = client(ClientConfig::builder().base_url(&uri));
+            assert!(matches!(untrusted.version().await, Err(OllamaError::NetworkError(..))));
+
+            let trusted =
+                client(ClientConfig::builder().base_url(&uri).ca_bundle(pki.path("ca.pem")));
+            assert_eq!(trusted.version().await.unwrap()["version"], "0.6.0");
+        }
+
+        #[tokio::test]
+        async fn test_mutual_tls() {
+            let pki = TestPki::generate();
+            let uri = pki.serve(true);
+            let builder = || ClientConfig::builder().base_url(&uri).ca_bundle(pki.path("ca.pem"));
+
+            let anonymous = client(builder());
+            assert!(anonymous.version().await.is_err());
+
+            let separate_key = client(
+                builder().client_identity(pki.path("client.crt"), Some(pki.path("client.key"))),
+            );
+            assert_eq!(separate_key.version().await.unwrap()["version"], "0.6.0");
+
+            let combined = client(builder().client_identity(pki.path("client.pem"), None));
+            assert_eq!(combined.version().await.unwrap()["version"], "0.6.0");
+        }
+
+        #[test]
+        fn test_invalid_tls_files() {
+            let pki = TestPki::generate();
+
+            let missing = ClientConfig::builder().ca_bundle(pki.path("missing.pem")).build();
+            let result = OllamaClient::with_config(missing.unwrap());
+            assert!(
+                matches!(result, Err(OllamaError::ConfigError(msg)) if msg.contains("missing.pem"))
+            );
+
+            let no_key = ClientConfig::builder().client_identity(pki.path("client.crt"), None);
+            let result = OllamaClient::with_config(no_key.build().unwrap());
+            assert!(matches!(result, Err(OllamaError::ConfigError(_))));
+        }
+    }
+}